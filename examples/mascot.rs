@@ -4,8 +4,9 @@ struct Mascot;
 
 impl App for Mascot {
     fn display(&mut self, ui: &mut Ui) {
+        let title = Gradient::new(&[Color::RGB(122, 98, 168), Color::RGB(188, 163, 225)]);
         ui
-            .ascii_art("Tuitui")
+            .component(AsciiArt::new("Tuitui").with_gradient(title))
             .text(text!(
                 span!("   _\n", color Color::RGB(122, 98, 168)),
                 span!(" (", color Color::RGB(122, 98, 168)),
@@ -22,18 +23,8 @@ impl App for Mascot {
     }
     
     fn update(&mut self, key: Option<Key>) -> bool {
-        if let None = key {
-            return true
-        }
-        let key = key.unwrap();
-
-        let mut quit = false;
-
-        match key.key {
-            InputKey::Esc => quit = true,
-            _ => {}
-        }
-        !quit
+        // Quit on Esc
+        key.map(|k| k.key != InputKey::Esc).unwrap_or(true)
     }
 
     fn get_fps(&self) -> u32 {
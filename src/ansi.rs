@@ -0,0 +1,255 @@
+use crate::components::{text::Color, Modifier, Style, UnderlineStyle};
+
+/// * The graphic state (colors and modifiers) selected by SGR escape sequences, and the open OSC 8 hyperlink
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SgrState {
+    pub style: Style,
+    pub link: Option<String>,
+}
+
+impl SgrState {
+
+    fn add(&mut self, modifier: Modifier) {
+        self.style.add_modifier.insert(modifier);
+    }
+
+
+    fn underline(&mut self, shape: UnderlineStyle) {
+        self.add(Modifier::Underlined);
+        self.style.underline_style = Some(shape);
+    }
+
+
+    fn remove(&mut self, modifiers: &[Modifier]) {
+        modifiers.iter().for_each(|m| self.style.add_modifier.remove(*m));
+    }
+
+
+    /// Applies the parameters of a single `ESC [ ... m` sequence
+    ///
+    /// Both the `38;5;n` and the `38:5:n` forms of extended colors are understood.
+    pub fn apply(&mut self, params: &str) {
+        let groups = params
+            .split(';')
+            .map(|group| group.split(':').map(|p| p.parse::<u16>().ok()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut i = 0;
+        while i < groups.len() {
+            let group = &groups[i];
+            match group[0].unwrap_or(0) {
+                0 => self.style = Style::default(),
+                1 => self.add(Modifier::Bold),
+                2 => self.add(Modifier::Dim),
+                3 => self.add(Modifier::Italic),
+                4 => match group.get(1).copied().flatten() {
+                    Some(0) => self.remove(&[Modifier::Underlined]),
+                    shape => self.underline(match shape {
+                        Some(2) => UnderlineStyle::Double,
+                        Some(3) => UnderlineStyle::Curly,
+                        Some(4) => UnderlineStyle::Dotted,
+                        Some(5) => UnderlineStyle::Dashed,
+                        _ => UnderlineStyle::Single,
+                    }),
+                },
+                21 => self.underline(UnderlineStyle::Double),
+                5 | 6 => self.add(Modifier::Blink),
+                7 => self.add(Modifier::ReverseVideo),
+                8 => self.add(Modifier::Concealed),
+                9 => self.add(Modifier::Strikethrough),
+                22 => self.remove(&[Modifier::Bold, Modifier::Dim]),
+                23 => self.remove(&[Modifier::Italic]),
+                24 => self.remove(&[Modifier::Underlined]),
+                25 => self.remove(&[Modifier::Blink]),
+                27 => self.remove(&[Modifier::ReverseVideo]),
+                28 => self.remove(&[Modifier::Concealed]),
+                29 => self.remove(&[Modifier::Strikethrough]),
+                c @ 30..=37 => self.style.fg = Some(named_color(c - 30)),
+                39 => self.style.fg = None,
+                c @ 40..=47 => self.style.bg = Some(named_color(c - 40)),
+                49 => self.style.bg = None,
+                59 => self.style.underline_color = None,
+                c @ 90..=97 => self.style.fg = Some(named_color(c - 90 + 8)),
+                c @ 100..=107 => self.style.bg = Some(named_color(c - 100 + 8)),
+                c @ (38 | 48 | 58) => {
+                    let (color, used) = if group.len() > 1 {
+                        // Colon form, everything is in this group. The truecolor form may carry a color space id
+                        let args = &group[1..];
+                        let color = match args {
+                            [Some(5), Some(n), ..] => Some(indexed_color(*n)),
+                            [Some(2), _, Some(r), Some(g), Some(b), ..] | [Some(2), Some(r), Some(g), Some(b)] => {
+                                Some(Color::RGB(*r as u8, *g as u8, *b as u8))
+                            }
+                            _ => None,
+                        };
+                        (color, 0)
+                    } else {
+                        let arg = |n: usize| groups.get(i + n).and_then(|g| g[0]);
+                        match arg(1) {
+                            Some(5) => (arg(2).map(indexed_color), 2),
+                            Some(2) => match (arg(2), arg(3), arg(4)) {
+                                (Some(r), Some(g), Some(b)) => (Some(Color::RGB(r as u8, g as u8, b as u8)), 4),
+                                _ => (None, groups.len()),
+                            },
+                            _ => (None, groups.len()),
+                        }
+                    };
+                    match c {
+                        38 => self.style.fg = color.or(self.style.fg),
+                        48 => self.style.bg = color.or(self.style.bg),
+                        _ => self.style.underline_color = color.or(self.style.underline_color),
+                    }
+                    i += used;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// * Maps an index of the 256 color palette to a `Color`, using the named colors for the first 16
+pub(crate) fn indexed_color(index: u16) -> Color {
+    match index {
+        0..=15 => named_color(index),
+        _ => Color::Indexed(index.min(255) as u8),
+    }
+}
+
+/// * Maps an index of the 16 color ANSI palette to its named `Color`
+pub(crate) fn named_color(index: u16) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::White,
+        8 => Color::Gray,
+        9 => Color::BrightRed,
+        10 => Color::Lime,
+        11 => Color::Banana,
+        12 => Color::LightBlue,
+        13 => Color::Pink,
+        14 => Color::LightCyan,
+        _ => Color::BrightWhite,
+    }
+}
+
+/// The OSC 8 sequence opening a hyperlink to `url`, or closing the open one when `url` is empty
+pub(crate) fn hyperlink(url: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\", url)
+}
+
+/// The OSC 52 sequence putting `text` on the system clipboard
+pub(crate) fn clipboard(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, b)| group | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(group >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+/// * Guesses whether the terminal shows OSC 8 hyperlinks, from `FORCE_HYPERLINK` and the variables terminals set
+pub(crate) fn detect_hyperlinks() -> bool {
+    detect_hyperlinks_from(|name| std::env::var(name).ok())
+}
+
+
+pub(crate) fn detect_hyperlinks_from<F: Fn(&str) -> Option<String>>(var: F) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return !matches!(force.trim(), "0" | "false" | "");
+    }
+    if ["WT_SESSION", "KITTY_WINDOW_ID", "WEZTERM_EXECUTABLE", "KONSOLE_VERSION", "DOMTERM"].iter().any(|v| var(v).is_some()) {
+        return true;
+    }
+    if var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()).is_some_and(|v| v >= 5000) {
+        return true;
+    }
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+    if ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"].contains(&program.as_str()) {
+        return true;
+    }
+    let term = var("TERM").unwrap_or_default();
+    ["kitty", "alacritty", "foot", "wezterm", "ghostty"].iter().any(|t| term.contains(t))
+}
+
+/// * Splits a string containing SGR escape sequences into runs of text and the state they are drawn with
+///
+/// OSC 8 hyperlinks are followed too. All other escape sequences (cursor movement, OSC titles, ...) are dropped.
+pub(crate) fn segments(input: &str) -> Vec<(SgrState, String)> {
+    let mut state = SgrState::default();
+    let mut output: Vec<(SgrState, String)> = Vec::new();
+    let mut current = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            current.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                let mut terminator = None;
+                for p in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&p) {
+                        terminator = Some(p);
+                        break;
+                    }
+                    params.push(p);
+                }
+
+                if terminator == Some('m') && !params.starts_with(['?', '<', '=', '>']) {
+                    if !current.is_empty() {
+                        output.push((state.clone(), std::mem::take(&mut current)));
+                    }
+                    state.apply(&params);
+                }
+            }
+            // OSC, DCS, APC, PM and SOS strings end with BEL or ST (`ESC \`). Only OSC 8 hyperlinks are kept
+            Some(kind @ (']' | 'P' | '_' | '^' | 'X')) => {
+                let mut body = String::new();
+                while let Some(p) = chars.next() {
+                    if p == '\x07' || (p == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                    body.push(p);
+                }
+                if let Some(link) = body.strip_prefix("8;").filter(|_| kind == ']') {
+                    let url = link.split_once(';').map_or("", |(_, url)| url);
+                    if !current.is_empty() {
+                        output.push((state.clone(), std::mem::take(&mut current)));
+                    }
+                    state.link = (!url.is_empty()).then(|| url.to_string());
+                }
+            }
+            // Character set selection takes one more character
+            Some('(' | ')' | '*' | '+' | '#' | '%') => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+
+    if !current.is_empty() {
+        output.push((state, current));
+    }
+    output
+}
@@ -1,80 +1,280 @@
-use std::io::{self, Write};
-use crate::{inputs::*, ui::Ui};
+use std::io;
+#[cfg(any(feature = "record", feature = "theme-files"))]
+use std::path::PathBuf;
+use std::time::Duration;
+use crate::{backend::*, buffer::Buffer, color_depth::ColorDepth, event::Event, inputs::*, theme::Theme, ui::Ui};
+#[cfg(feature = "record")]
+use crate::record::*;
 
 /// * Defines a TUI application that can handle input and render UI.
 ///
 /// Implement this trait to create your application logic:
 /// ```no_run
+/// # use tuitui::prelude::*;
 /// struct MyApp;
-/// 
+///
 /// impl App for MyApp {
 ///     fn display(&mut self, ui: &mut Ui) {
-///         ui.heading("My Cool App");
+///         ui.heading("My Cool App".into());
 ///     }
-///     
+///
 ///     fn update(&mut self, key: Option<Key>) -> bool {
 ///         // Return false to quit the App
 ///         key.map(|k| k.key != InputKey::Q).unwrap_or(true)
 ///     }
-/// 
+///
 ///     fn get_fps(&self) -> u32 {
 ///         30
 ///     }
 /// }
 /// ```
-
 pub trait App {
     fn display(&mut self, ui: &mut Ui);
     fn update(&mut self, key: Option<Key>) -> bool;
     fn get_fps(&self) -> u32;
 }
 
+#[derive(Default)]
 pub struct AppRuntime {
-    input: InputHandler
+    #[cfg(feature = "record")]
+    recording: Option<PathBuf>,
+    color_depth: Option<ColorDepth>,
+    theme: Option<Theme>,
+    hyperlinks: Option<bool>,
+    #[cfg(feature = "theme-files")]
+    theme_file: Option<PathBuf>
 }
 
 impl AppRuntime {
     pub fn new() -> Self {
         Self {
-            input: InputHandler::new()
+            #[cfg(feature = "record")]
+            recording: None,
+            color_depth: None,
+            theme: None,
+            hyperlinks: None,
+            #[cfg(feature = "theme-files")]
+            theme_file: None
         }
     }
-    
+
     pub fn with_title(self, _title: &str) -> Self {
         self
     }
-    
-    
-    pub fn run<A: App>(mut self, mut app: A) -> Result<(), Box<dyn std::error::Error>> {
-        let mut ui = Ui::new();
-        let mut last_frame = String::new();
-        
-        loop {
-            let wait = std::time::Duration::from_millis((1000 / app.get_fps()).into());
-            let input = self.input.poll(std::time::Duration::ZERO);
-            let current_frame = ui.render();
 
-            if !app.update(input) {
-                break;
+
+    /// Records every frame and input event of `run` to an asciicast v2 file
+    #[cfg(feature = "record")]
+    pub fn with_recording<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.recording = Some(path.into());
+        self
+    }
+
+
+    /// Draws with the given color depth instead of the one the backend detected
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_depth = Some(depth);
+        self
+    }
+
+
+    /// Starts the `Ui` with `theme`. The app can still switch it with `Ui::set_theme`
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+
+    /// Sets whether the terminal shows OSC 8 hyperlinks, instead of guessing it from the environment
+    pub fn with_hyperlinks(mut self, supported: bool) -> Self {
+        self.hyperlinks = Some(supported);
+        self
+    }
+
+
+    /// Loads the theme from a TOML or JSON file (see `Theme::from_toml`), and reloads it whenever the file is saved.
+    /// A save that doesn't load keeps the last theme, and the app can see why with `Ui::theme_error`
+    #[cfg(feature = "theme-files")]
+    pub fn with_theme_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.theme_file = Some(path.into());
+        self
+    }
+
+
+    #[cfg(feature = "record")]
+    pub fn run<A: App>(mut self, app: A) -> Result<(), Box<dyn std::error::Error>> {
+        match self.recording.take() {
+            Some(path) => {
+                let backend = RecordingBackend::create(CrosstermBackend::new(), path)?;
+                self.run_with(app, backend)
+            }
+            None => self.run_with(app, CrosstermBackend::new()),
+        }
+    }
+
+
+    #[cfg(not(feature = "record"))]
+    pub fn run<A: App>(self, app: A) -> Result<(), Box<dyn std::error::Error>> {
+        self.run_with(app, CrosstermBackend::new())
+    }
+
+
+    /// Feeds the input of an asciicast recording back into `app` at its recorded pace, quitting when it runs out
+    #[cfg(feature = "record")]
+    pub fn replay<A: App, P: AsRef<std::path::Path>>(self, app: A, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        let recording = Recording::load(path)?;
+        self.run_with(app, ReplayBackend::new(&recording, CrosstermBackend::new()))
+    }
+
+
+    pub fn run_with<A: App, B: Backend>(self, mut app: A, mut backend: B) -> Result<(), Box<dyn std::error::Error>> {
+        let mut frames = FrameLoop::new();
+        frames.set_hyperlinks(self.hyperlinks.unwrap_or_else(crate::ansi::detect_hyperlinks));
+        if let Some(theme) = self.theme {
+            frames.set_theme(theme);
+        }
+        #[cfg(feature = "theme-files")]
+        let mut watcher = match self.theme_file {
+            Some(path) => {
+                let (watcher, theme) = crate::theme_file::ThemeWatcher::new(path)?;
+                frames.set_theme(theme);
+                Some(watcher)
+            }
+            None => None,
+        };
+        if let Some(depth) = self.color_depth {
+            backend.set_color_depth(depth);
+        }
+        backend.hide_cursor()?;
+        let guard = CursorGuard(&mut backend);
+
+        loop {
+            let wait = Duration::from_millis((1000 / app.get_fps()).into());
+            #[cfg(feature = "theme-files")]
+            if let Some(result) = watcher.as_mut().and_then(|w| w.poll()) {
+                frames.reload_theme(result);
             }
-            
-            ui.clear();
-            app.display(&mut ui);
-            if current_frame != last_frame {
-                clear_screen();
-                print!("{}", current_frame);
-                io::stdout().flush().unwrap();
-                last_frame = current_frame;
+            if !frames.step(&mut app, &mut *guard.0, Duration::ZERO)? {
+                break;
             }
-            
+
             std::thread::sleep(wait);
         }
-        
+
+        drop(guard);
         Ok(())
     }
 }
 
+/// Shows the cursor again however `run_with` ends, even when a frame fails or the app panics
+struct CursorGuard<'a, B: Backend>(&'a mut B);
+
+impl<B: Backend> Drop for CursorGuard<'_, B> {
+
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+        let _ = self.0.flush();
+    }
+}
+
+/// * Runs single frames of an `App` against a `Backend`: poll one event, update, display and draw if anything changed
+pub(crate) struct FrameLoop {
+    ui: Ui,
+    last_frame: Option<Buffer>,
+    /// The last frame with revealed password inputs masked, when it had any
+    public_frame: Option<Buffer>,
+    sensitive: bool,
+}
+
+impl FrameLoop {
+
+    pub(crate) fn new() -> Self {
+        Self {
+            ui: Ui::new(),
+            last_frame: None,
+            public_frame: None,
+            sensitive: false,
+        }
+    }
+
+
+    /// The last frame as recordings and snapshots may see it, if it differs from the one drawn
+    pub(crate) fn public_frame(&self) -> Option<&Buffer> {
+        self.public_frame.as_ref()
+    }
+
+
+    pub(crate) fn set_theme(&mut self, theme: Theme) {
+        self.ui.set_theme(theme);
+    }
+
 
-fn clear_screen() {
-    print!("\x1b[2J\x1b[3J\x1b[1;1H"); 
-}
\ No newline at end of file
+    /// Switches to a reloaded theme, or keeps the current one and lets the app see why it didn't load
+    #[cfg(feature = "theme-files")]
+    pub(crate) fn reload_theme(&mut self, result: Result<Theme, crate::theme_file::ThemeError>) {
+        match result {
+            Ok(theme) => {
+                self.ui.set_theme(theme);
+                self.ui.theme_error = None;
+            }
+            Err(error) => self.ui.theme_error = Some(error),
+        }
+    }
+
+
+    pub(crate) fn set_hyperlinks(&mut self, supported: bool) {
+        self.ui.set_hyperlinks(supported);
+    }
+
+
+    /// Returns `false` once the app has quit
+    pub(crate) fn step<A: App, B: Backend>(&mut self, app: &mut A, backend: &mut B, timeout: Duration) -> io::Result<bool> {
+        let key = match backend.poll_event(timeout)? {
+            Some(Event::Key(key)) => Some(key),
+            Some(Event::Quit) => return Ok(false),
+            Some(Event::Resize(_, _)) => {
+                self.last_frame = None;
+                None
+            }
+            None => None,
+        };
+
+        if !app.update(key) {
+            return Ok(false);
+        }
+
+        let (width, height) = backend.size()?;
+        self.ui.clear();
+        self.ui.width = Some(width);
+        app.display(&mut self.ui);
+        if let Some(text) = self.ui.clipboard.take() {
+            backend.set_clipboard(&text)?;
+        }
+        backend.set_sensitive(self.ui.sensitive);
+        // Recordings skip sensitive frames, so the first one after them is drawn whole
+        if self.sensitive && !self.ui.sensitive {
+            self.last_frame = None;
+        }
+        self.sensitive = self.ui.sensitive;
+        let current_frame = self.ui.to_buffer(width, height);
+        self.public_frame = (!self.ui.masked.is_empty()).then(|| self.ui.to_public_buffer(width, height));
+
+        match &self.last_frame {
+            Some(last_frame) if last_frame.size() == current_frame.size() => {
+                let changes = current_frame.diff(last_frame);
+                if !changes.is_empty() {
+                    backend.draw(changes.into_iter())?;
+                    backend.flush()?;
+                }
+            }
+            _ => {
+                backend.clear()?;
+                backend.draw(current_frame.content())?;
+                backend.flush()?;
+            }
+        }
+        self.last_frame = Some(current_frame);
+
+        Ok(true)
+    }
+}
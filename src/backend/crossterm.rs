@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+use std::time::Duration;
+use crossterm::{cursor, queue, style, terminal};
+use crate::{ansi, buffer::Cell, color_depth::ColorDepth, event::Event, inputs::InputHandler};
+use super::{write_cells, Backend};
+
+/// * Draws through crossterm commands and reads keys from the terminal
+///
+/// This is the default backend used by `AppRuntime::run`. Output goes to stdout unless another writer is given,
+/// with the `ColorDepth` detected from the environment.
+pub struct CrosstermBackend<W: Write = io::Stdout> {
+    writer: W,
+    input: InputHandler,
+    color_depth: ColorDepth
+}
+
+impl CrosstermBackend<io::Stdout> {
+
+    pub fn new() -> Self {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl Default for CrosstermBackend<io::Stdout> {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> CrosstermBackend<W> {
+
+    pub fn with_writer(writer: W) -> Self {
+        Self {
+            writer,
+            input: InputHandler::new(),
+            color_depth: ColorDepth::detect()
+        }
+    }
+
+
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+}
+
+impl<W: Write> Backend for CrosstermBackend<W> {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        write_cells(&mut self.writer, content, self.color_depth)
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        queue!(self.writer, cursor::MoveTo(x, y))
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        queue!(self.writer, cursor::Hide)
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        queue!(self.writer, cursor::Show)
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        queue!(
+            self.writer,
+            terminal::Clear(terminal::ClearType::All),
+            terminal::Clear(terminal::ClearType::Purge),
+            cursor::MoveTo(0, 0)
+        )
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        Ok(self.input.poll_event(timeout))
+    }
+
+
+    fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.color_depth = depth;
+    }
+
+
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        queue!(self.writer, style::Print(ansi::clipboard(text)))
+    }
+}
@@ -0,0 +1,163 @@
+pub mod crossterm;
+pub mod test;
+#[cfg(feature = "ansi")]
+pub mod writer;
+#[cfg(feature = "termion")]
+pub mod termion;
+
+pub use self::crossterm::CrosstermBackend;
+pub use test::TestBackend;
+#[cfg(feature = "ansi")]
+pub use writer::AnsiBackend;
+#[cfg(feature = "termion")]
+pub use self::termion::TermionBackend;
+
+use std::io;
+use std::time::Duration;
+use crate::{ansi, buffer::Cell, color_depth::ColorDepth, components::Style, event::Event};
+
+/// * Defines where frames are drawn and where input comes from
+///
+/// `AppRuntime::run` uses the `CrosstermBackend`, which talks to the real terminal.
+/// Use `AppRuntime::run_with` to drive an `App` through any other backend:
+///
+/// | Backend | Feature | Output | Input |
+/// |---|---|---|---|
+/// | `CrosstermBackend` | always | any writer, stdout by default | the terminal |
+/// | `TermionBackend` | `termion` | any writer, stdout by default | the terminal |
+/// | `AnsiBackend` | `ansi` | any writer | any reader, decoded by `InputDecoder` |
+/// | `TestBackend` | always | an in-memory `Buffer` | scripted events |
+pub trait Backend {
+    /// Draws the given cells; `x` and `y` are zero based
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>;
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()>;
+    fn hide_cursor(&mut self) -> io::Result<()>;
+    fn show_cursor(&mut self) -> io::Result<()>;
+    fn clear(&mut self) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
+    fn size(&self) -> io::Result<(u16, u16)>;
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>;
+
+    /// Maps colors to the nearest ones available at `depth`. Backends that don't write to a terminal ignore it
+    fn set_color_depth(&mut self, _depth: ColorDepth) {}
+
+    /// Puts text on the system clipboard with OSC 52. Backends that don't write to a terminal ignore it
+    fn set_clipboard(&mut self, _text: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Told before every frame is drawn whether it's secret, like a password input. Backends that record what goes
+    /// through them leave out secret frames and the keys that lead to them or are pressed on them
+    fn set_sensitive(&mut self, _sensitive: bool) {}
+}
+
+impl<B: Backend> Backend for &mut B {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        (**self).draw(content)
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        (**self).move_cursor(x, y)
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        (**self).hide_cursor()
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        (**self).show_cursor()
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        (**self).clear()
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        (**self).size()
+    }
+
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        (**self).poll_event(timeout)
+    }
+
+
+    fn set_color_depth(&mut self, depth: ColorDepth) {
+        (**self).set_color_depth(depth)
+    }
+
+
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        (**self).set_clipboard(text)
+    }
+
+
+    fn set_sensitive(&mut self, sensitive: bool) {
+        (**self).set_sensitive(sensitive)
+    }
+}
+
+/// * Writes cells as ANSI escape sequences, only moving the cursor and changing styles when needed
+///
+/// Shared by the backends that talk to a terminal through a plain writer. Colors are reduced to `depth`.
+pub(crate) fn write_cells<'a, W, I>(writer: &mut W, content: I, depth: ColorDepth) -> io::Result<()>
+where
+    W: io::Write,
+    I: Iterator<Item = (u16, u16, &'a Cell)>,
+{
+    let mut cursor: Option<(u16, u16)> = None;
+    // The terminal's style is unknown until the first cell resets it
+    let mut current: Option<Style> = None;
+    let mut link: Option<&str> = None;
+
+    for (x, y, cell) in content {
+        // Covered by the double width character drawn before it
+        if cell.is_continuation() {
+            continue;
+        }
+        if cursor != Some((x, y)) {
+            write!(writer, "\x1b[{};{}H", y + 1, x + 1)?;
+        }
+
+        if cell.link.as_deref() != link {
+            writer.write_all(ansi::hyperlink(cell.link.as_deref().unwrap_or("")).as_bytes())?;
+            link = cell.link.as_deref();
+        }
+        let style = depth.reduce_style(cell.style());
+        if current != Some(style) {
+            let sgr = match current {
+                Some(previous) => style.sgr(&previous),
+                None => format!("\x1b[0m{}", style.sgr(&Style::new())),
+            };
+            writer.write_all(sgr.as_bytes())?;
+            current = Some(style);
+        }
+
+        writer.write_all(cell.symbol.as_bytes())?;
+        cursor = Some((x + cell.width(), y));
+    }
+
+    if current.is_some() {
+        writer.write_all(b"\x1b[0m")?;
+    }
+    if link.is_some() {
+        writer.write_all(ansi::hyperlink("").as_bytes())?;
+    }
+    Ok(())
+}
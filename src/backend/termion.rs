@@ -0,0 +1,166 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use termion::{event::Key as TermionKey, input::{Keys, TermRead}, AsyncReader};
+use crate::{ansi, buffer::Cell, color_depth::ColorDepth, event::Event, inputs::*};
+use super::{write_cells, Backend};
+
+/// * Draws with ANSI escape sequences and reads keys through termion
+///
+/// Output goes to stdout unless another writer is given, with the `ColorDepth` detected from the environment.
+/// Only available on unix platforms.
+pub struct TermionBackend<W: Write = io::Stdout> {
+    writer: W,
+    keys: Keys<AsyncReader>,
+    color_depth: ColorDepth,
+}
+
+impl TermionBackend<io::Stdout> {
+
+    pub fn new() -> Self {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl Default for TermionBackend<io::Stdout> {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> TermionBackend<W> {
+
+    pub fn with_writer(writer: W) -> Self {
+        Self {
+            writer,
+            keys: termion::async_stdin().keys(),
+            color_depth: ColorDepth::detect(),
+        }
+    }
+}
+
+impl<W: Write> Backend for TermionBackend<W> {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        write_cells(&mut self.writer, content, self.color_depth)
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        write!(self.writer, "{}", termion::cursor::Goto(x + 1, y + 1))
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        write!(self.writer, "{}", termion::cursor::Hide)
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        write!(self.writer, "{}", termion::cursor::Show)
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        write!(self.writer, "{}{}", termion::clear::All, termion::cursor::Goto(1, 1))
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        termion::terminal_size()
+    }
+
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let start = Instant::now();
+        loop {
+            if let Some(key) = self.keys.next() {
+                if let Some(key) = convert_key(key?) {
+                    return Ok(Some(Event::Key(key)));
+                }
+                continue;
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            std::thread::sleep(Duration::from_millis(5).min(timeout));
+        }
+    }
+
+
+    fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.color_depth = depth;
+    }
+
+
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        self.writer.write_all(ansi::clipboard(text).as_bytes())
+    }
+}
+
+fn convert_key(key: TermionKey) -> Option<Key> {
+    let with = |key: InputKey, modifiers: KeyModifiers| Some(Key::new(key).with_modifiers(modifiers));
+    let none = KeyModifiers::NONE;
+
+    match key {
+        TermionKey::Char('\n') => with(InputKey::Enter, none),
+        TermionKey::Char('\t') => with(InputKey::Tab, none),
+        TermionKey::Char(c) => Key::from_char(c),
+        TermionKey::Alt(c) => Key::from_char(c).map(|k| k.with_modifiers(k.modifiers | KeyModifiers::ALT)),
+        TermionKey::Ctrl(c) => Key::from_char(c).map(|k| k.with_modifiers(KeyModifiers::CONTROL)),
+        TermionKey::Backspace => with(InputKey::Backspace, none),
+        TermionKey::Left => with(InputKey::LeftArrow, none),
+        TermionKey::ShiftLeft => with(InputKey::LeftArrow, KeyModifiers::SHIFT),
+        TermionKey::AltLeft => with(InputKey::LeftArrow, KeyModifiers::ALT),
+        TermionKey::CtrlLeft => with(InputKey::LeftArrow, KeyModifiers::CONTROL),
+        TermionKey::Right => with(InputKey::RightArrow, none),
+        TermionKey::ShiftRight => with(InputKey::RightArrow, KeyModifiers::SHIFT),
+        TermionKey::AltRight => with(InputKey::RightArrow, KeyModifiers::ALT),
+        TermionKey::CtrlRight => with(InputKey::RightArrow, KeyModifiers::CONTROL),
+        TermionKey::Up => with(InputKey::UpArrow, none),
+        TermionKey::ShiftUp => with(InputKey::UpArrow, KeyModifiers::SHIFT),
+        TermionKey::AltUp => with(InputKey::UpArrow, KeyModifiers::ALT),
+        TermionKey::CtrlUp => with(InputKey::UpArrow, KeyModifiers::CONTROL),
+        TermionKey::Down => with(InputKey::DownArrow, none),
+        TermionKey::ShiftDown => with(InputKey::DownArrow, KeyModifiers::SHIFT),
+        TermionKey::AltDown => with(InputKey::DownArrow, KeyModifiers::ALT),
+        TermionKey::CtrlDown => with(InputKey::DownArrow, KeyModifiers::CONTROL),
+        TermionKey::Home => with(InputKey::Home, none),
+        TermionKey::CtrlHome => with(InputKey::Home, KeyModifiers::CONTROL),
+        TermionKey::End => with(InputKey::End, none),
+        TermionKey::CtrlEnd => with(InputKey::End, KeyModifiers::CONTROL),
+        TermionKey::PageUp => with(InputKey::PageUp, none),
+        TermionKey::PageDown => with(InputKey::PageDown, none),
+        TermionKey::BackTab => with(InputKey::BackTab, none),
+        TermionKey::Delete => with(InputKey::Delete, none),
+        TermionKey::Insert => with(InputKey::Insert, none),
+        TermionKey::Esc => with(InputKey::Esc, none),
+        TermionKey::F(n) => {
+            let key = match n {
+                1 => InputKey::F1,
+                2 => InputKey::F2,
+                3 => InputKey::F3,
+                4 => InputKey::F4,
+                5 => InputKey::F5,
+                6 => InputKey::F6,
+                7 => InputKey::F7,
+                8 => InputKey::F8,
+                9 => InputKey::F9,
+                10 => InputKey::F10,
+                11 => InputKey::F11,
+                12 => InputKey::F12,
+                _ => return None,
+            };
+            with(key, none)
+        }
+        _ => None,
+    }
+}
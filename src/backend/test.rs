@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+use crate::{buffer::{Buffer, Cell}, event::Event};
+use super::Backend;
+
+/// * An in-memory backend for running apps without a terminal
+///
+/// Events are queued with `push_event` and handed out one per poll; drawn cells are kept in a `Buffer`.
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut backend = TestBackend::new(20, 5);
+/// backend.push_event(Event::Key(Key::new(InputKey::Esc)));
+/// assert_eq!(backend.buffer().size(), (20, 5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestBackend {
+    buffer: Buffer,
+    events: VecDeque<Event>,
+    cursor: (u16, u16),
+    cursor_visible: bool,
+    frames_drawn: usize,
+    clipboard: Option<String>,
+    sensitive: bool,
+}
+
+impl TestBackend {
+
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            buffer: Buffer::new(width, height),
+            events: VecDeque::new(),
+            cursor: (0, 0),
+            cursor_visible: true,
+            frames_drawn: 0,
+            clipboard: None,
+            sensitive: false,
+        }
+    }
+
+
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push_back(event);
+    }
+
+
+    pub fn pending_events(&self) -> usize {
+        self.events.len()
+    }
+
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+
+    pub fn cursor(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+
+    /// The number of times `draw` has been called
+    pub fn frames_drawn(&self) -> usize {
+        self.frames_drawn
+    }
+
+
+    /// The text last put on the clipboard
+    pub fn clipboard(&self) -> Option<&str> {
+        self.clipboard.as_deref()
+    }
+
+
+    /// Whether the last frame was sensitive, see `Backend::set_sensitive`
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.buffer.resize(width, height);
+    }
+}
+
+impl Backend for TestBackend {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        for (x, y, cell) in content {
+            if let Some(target) = self.buffer.get_mut(x, y) {
+                *target = cell.clone();
+            }
+        }
+        self.frames_drawn += 1;
+        Ok(())
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.cursor = (x, y);
+        Ok(())
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.cursor_visible = false;
+        Ok(())
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.cursor_visible = true;
+        Ok(())
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.buffer.reset();
+        Ok(())
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.buffer.size())
+    }
+
+
+    fn poll_event(&mut self, _timeout: Duration) -> io::Result<Option<Event>> {
+        let event = self.events.pop_front();
+        if let Some(Event::Resize(width, height)) = event {
+            self.resize(width, height);
+        }
+        Ok(event)
+    }
+
+
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        self.clipboard = Some(text.to_string());
+        Ok(())
+    }
+
+
+    fn set_sensitive(&mut self, sensitive: bool) {
+        self.sensitive = sensitive;
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Read, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use crate::{ansi, buffer::Cell, color_depth::ColorDepth, event::Event, inputs::InputDecoder};
+use super::{write_cells, Backend};
+
+/// * Writes raw ANSI escape sequences to any writer, like a socket or a file
+///
+/// The size of the screen is whatever it is told, since a plain writer has no way of knowing it.
+/// Input is optional: give it a reader to decode keys from, or a channel of already decoded events.
+/// A `Resize` event coming through either one also resizes the backend.
+/// Colors are written as truecolor unless another `ColorDepth` is set, since the terminal on the other end is unknown.
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut backend = AnsiBackend::new(Vec::new(), 80, 24);
+/// backend.draw([(0, 0, &Cell::new("x"))].into_iter()).unwrap();
+/// assert!(backend.writer().ends_with(b"x\x1b[0m"));
+/// ```
+pub struct AnsiBackend<W: Write> {
+    writer: W,
+    size: (u16, u16),
+    events: Option<Receiver<Event>>,
+    color_depth: ColorDepth,
+}
+
+impl<W: Write> AnsiBackend<W> {
+
+    pub fn new(writer: W, width: u16, height: u16) -> Self {
+        Self {
+            writer,
+            size: (width, height),
+            events: None,
+            color_depth: ColorDepth::TrueColor,
+        }
+    }
+
+
+    /// Decodes keys and window size reports from `reader` on a background thread. The end of the stream is reported as `Event::Quit`
+    pub fn with_input<R: Read + Send + 'static>(self, mut reader: R) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut decoder = InputDecoder::new();
+            let mut buf = [0u8; 1024];
+            loop {
+                let n = match reader.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                let mut events = decoder.feed_events(&buf[..n]);
+                if buf[n - 1] == 0x1b {
+                    events.extend(decoder.flush_events());
+                }
+                if events.into_iter().any(|e| sender.send(e).is_err()) {
+                    return;
+                }
+            }
+            let _ = sender.send(Event::Quit);
+        });
+
+        self.with_events(receiver)
+    }
+
+
+    pub fn with_events(mut self, events: Receiver<Event>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+
+    pub fn set_size(&mut self, width: u16, height: u16) {
+        self.size = (width, height);
+    }
+
+
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Backend for AnsiBackend<W> {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        write_cells(&mut self.writer, content, self.color_depth)
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        write!(self.writer, "\x1b[{};{}H", y + 1, x + 1)
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[?25l")
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[?25h")
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[2J\x1b[3J\x1b[1;1H")
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.size)
+    }
+
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let Some(events) = &self.events else {
+            return Ok(None);
+        };
+
+        let event = match events.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => return Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                self.events = None;
+                return Ok(Some(Event::Quit));
+            }
+        };
+
+        if let Event::Resize(width, height) = event {
+            self.set_size(width, height);
+        }
+        Ok(Some(event))
+    }
+
+
+    fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.color_depth = depth;
+    }
+
+
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        self.writer.write_all(ansi::clipboard(text).as_bytes())
+    }
+}
@@ -0,0 +1,97 @@
+//! Attaches the current terminal to a tuitui `AppServer`.
+//!
+//! ```text
+//! tuitui-attach 127.0.0.1:7000
+//! tuitui-attach /tmp/dashboard.sock
+//! ```
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use crossterm::terminal;
+
+type Error = Box<dyn std::error::Error>;
+type Connection = (Box<dyn Read + Send>, Box<dyn Write + Send>);
+type SharedWriter = Arc<Mutex<Box<dyn Write + Send>>>;
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("tuitui-attach: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let target = std::env::args()
+        .nth(1)
+        .ok_or("usage: tuitui-attach <host:port | unix socket path>")?;
+    let (reader, writer) = connect(&target)?;
+
+    terminal::enable_raw_mode()?;
+    let result = attach(reader, Arc::new(Mutex::new(writer)));
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn connect(target: &str) -> Result<Connection, Error> {
+    #[cfg(unix)]
+    if let Some(path) = target.strip_prefix("unix:").or(target.contains('/').then_some(target)) {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        return Ok((Box::new(stream.try_clone()?), Box::new(stream)));
+    }
+
+    let stream = TcpStream::connect(target.strip_prefix("tcp:").unwrap_or(target))?;
+    stream.set_nodelay(true)?;
+    Ok((Box::new(stream.try_clone()?), Box::new(stream)))
+}
+
+fn attach(mut reader: Box<dyn Read + Send>, writer: SharedWriter) -> Result<(), Error> {
+    let mut size = terminal::size()?;
+    send_size(&writer, size)?;
+
+    // Forward keys exactly as the terminal sends them
+    let input = Arc::clone(&writer);
+    thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut buf = [0u8; 1024];
+        while let Ok(n) = stdin.read(&mut buf) {
+            if n == 0 || input.lock().unwrap().write_all(&buf[..n]).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Report every change to the window size
+    let resize = Arc::clone(&writer);
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_millis(200));
+        if let Ok(current) = terminal::size() {
+            if current != size {
+                size = current;
+                if send_size(&resize, size).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut stdout = io::stdout();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        stdout.write_all(&buf[..n])?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+fn send_size(writer: &SharedWriter, (width, height): (u16, u16)) -> io::Result<()> {
+    let mut writer = writer.lock().unwrap();
+    write!(writer, "\x1b[8;{};{}t", height, width)?;
+    writer.flush()
+}
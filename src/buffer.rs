@@ -0,0 +1,290 @@
+use crate::{ansi, backend, color_depth::ColorDepth, width};
+use crate::components::{text::Color, Modifiers, Style, UnderlineStyle};
+
+/// * A single character cell on the screen, with the colors and modifiers it is drawn with
+///
+/// A double width character takes two cells: the first holds the symbol, and the second is a
+/// continuation cell with an empty symbol that backends skip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: String,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub underline_color: Option<Color>,
+    /// The shape of the underline, only set when the cell is underlined
+    pub underline_style: Option<UnderlineStyle>,
+    pub modifiers: Modifiers,
+    /// The target of the OSC 8 hyperlink the cell is part of
+    pub link: Option<String>,
+}
+
+impl Cell {
+
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            fg: None,
+            bg: None,
+            underline_color: None,
+            underline_style: None,
+            modifiers: Modifiers::empty(),
+            link: None,
+        }
+    }
+
+
+    /// A cell drawn with the resolved colors and modifiers of `style`
+    pub fn styled(symbol: &str, style: Style) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            fg: style.fg,
+            bg: style.bg,
+            underline_color: style.underline_color,
+            underline_style: style.underline(),
+            modifiers: style.modifiers(),
+            link: None,
+        }
+    }
+
+
+    pub fn is_styled(&self) -> bool {
+        !self.style().is_plain()
+    }
+
+
+    pub fn style(&self) -> Style {
+        Style {
+            fg: self.fg,
+            bg: self.bg,
+            underline_color: self.underline_color,
+            underline_style: self.underline_style,
+            add_modifier: self.modifiers,
+            sub_modifier: Modifiers::empty(),
+        }
+    }
+
+
+    /// Whether the cell is covered by the double width character to its left
+    pub fn is_continuation(&self) -> bool {
+        self.symbol.is_empty()
+    }
+
+
+    /// The number of columns the symbol takes, 1 or 2
+    pub fn width(&self) -> u16 {
+        width::grapheme_width(&self.symbol).max(1) as u16
+    }
+}
+
+impl Default for Cell {
+
+    fn default() -> Self {
+        Self::new(" ")
+    }
+}
+
+/// * A grid of `Cell`s holding one rendered frame
+///
+/// Frames are produced by rendering a `Ui` and laying the output out on a screen of a fixed size:
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut ui = Ui::new();
+/// ui.text("Hello".into());
+///
+/// let buffer = Buffer::from_ansi(&ui.render(), 10, 2);
+/// assert_eq!(buffer.to_plain_string(), "Hello\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Buffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+
+    /// Lays out a string containing SGR escape sequences (like the output of `Ui::render`) from the top left corner
+    ///
+    /// Anything past the edges of the buffer is clipped.
+    pub fn from_ansi(frame: &str, width: u16, height: u16) -> Self {
+        let mut buffer = Self::new(width, height);
+        buffer.write_ansi(frame);
+        buffer
+    }
+
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+
+    pub fn get_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+
+    pub fn reset(&mut self) {
+        self.cells.iter_mut().for_each(|c| *c = Cell::default());
+    }
+
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        *self = Self::new(width, height);
+    }
+
+
+    /// Writes a string containing SGR escape sequences from the top left corner
+    ///
+    /// Double width characters take two cells, and one that would be cut by the right edge is drawn as a blank.
+    /// Zero width characters join the character before them.
+    pub fn write_ansi(&mut self, frame: &str) {
+        let (mut x, mut y) = (0u16, 0u16);
+
+        for (state, text) in ansi::segments(frame) {
+            for grapheme in width::graphemes(&text) {
+                match grapheme {
+                    "\n" | "\r\n" => {
+                        x = 0;
+                        y = y.saturating_add(1);
+                    }
+                    "\r" => x = 0,
+                    g if g.starts_with(char::is_control) => {}
+                    g => {
+                        x = x.saturating_add(self.put(x, y, Cell { link: state.link.clone(), ..Cell::styled(g, state.style) }));
+                    }
+                }
+            }
+        }
+    }
+
+
+    /// Puts a cell at the given position, returning how many columns it took
+    ///
+    /// Any double width character the cell overlaps is replaced by blanks, so none is left half drawn.
+    fn put(&mut self, x: u16, y: u16, cell: Cell) -> u16 {
+        let columns = width::grapheme_width(&cell.symbol) as u16;
+        if columns == 0 {
+            let mut previous = x.checked_sub(1);
+            if previous.and_then(|px| self.get(px, y)).is_some_and(Cell::is_continuation) {
+                previous = previous.and_then(|px| px.checked_sub(1));
+            }
+            if let Some(target) = previous.and_then(|px| self.get_mut(px, y)) {
+                target.symbol.push_str(&cell.symbol);
+            }
+            return 0;
+        }
+        if columns == 2 && x.saturating_add(1) >= self.width {
+            self.put(x, y, Cell { symbol: " ".to_string(), ..cell });
+            return 2;
+        }
+
+        for column in x..x.saturating_add(columns) {
+            self.clear_wide(column, y);
+        }
+        if let Some(target) = self.get_mut(x, y) {
+            *target = cell.clone();
+        }
+        if columns == 2 {
+            if let Some(target) = self.get_mut(x + 1, y) {
+                *target = Cell { symbol: String::new(), ..cell };
+            }
+        }
+        columns
+    }
+
+
+    /// Blanks out the double width character covering the given cell, if any
+    fn clear_wide(&mut self, x: u16, y: u16) {
+        let Some(cell) = self.get(x, y) else { return };
+        let start = if cell.is_continuation() { x.saturating_sub(1) } else { x };
+        if self.get(start, y).is_some_and(|c| c.width() == 2) {
+            for column in start..=start + 1 {
+                if let Some(c) = self.get_mut(column, y) {
+                    c.symbol = " ".to_string();
+                }
+            }
+        }
+    }
+
+
+    /// Every cell along with its position, row by row
+    pub fn content(&self) -> impl Iterator<Item = (u16, u16, &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| ((i % self.width as usize) as u16, (i / self.width as usize) as u16, c))
+    }
+
+
+    /// The cells that differ from `previous`, or every cell if the sizes differ
+    pub fn diff<'a>(&'a self, previous: &Buffer) -> Vec<(u16, u16, &'a Cell)> {
+        if self.size() != previous.size() {
+            return self.content().collect();
+        }
+        self.content()
+            .zip(previous.cells.iter())
+            .filter(|((_, _, current), previous)| current != previous)
+            .map(|(current, _)| current)
+            .collect()
+    }
+
+
+    /// Returns the symbols of one row, including trailing blanks
+    pub fn row(&self, y: u16) -> String {
+        (0..self.width)
+            .filter_map(|x| self.get(x, y))
+            .map(|c| c.symbol.as_str())
+            .collect()
+    }
+
+
+    /// Returns the text on screen with trailing blanks trimmed from every line
+    pub fn to_plain_string(&self) -> String {
+        (0..self.height)
+            .map(|y| self.row(y).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+
+    /// Returns the frame as escape sequences, positioning the cursor at the start of every row
+    pub fn to_ansi(&self) -> String {
+        let mut output = Vec::new();
+        backend::write_cells(&mut output, self.content(), ColorDepth::TrueColor).expect("writing to a Vec never fails");
+        String::from_utf8(output).expect("cells only hold valid UTF-8")
+    }
+
+
+    fn index_of(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| self.width as usize * y as usize + x as usize)
+    }
+}
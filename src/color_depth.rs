@@ -0,0 +1,119 @@
+use crate::{ansi, components::{text::Color, Style, UnderlineStyle}};
+
+/// * How many colors the terminal can show
+///
+/// Backends that talk to a terminal detect it from the environment, and map every `Color` to the nearest one
+/// the terminal supports. Override it with `AppRuntime::with_color_depth` or `Backend::set_color_depth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// No colors at all, styles like bold are still shown
+    NoColor,
+    /// The 16 named colors
+    Ansi16,
+    /// The 256 color xterm palette
+    Ansi256,
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+impl ColorDepth {
+
+    /// * Detects the color depth from `NO_COLOR`, `FORCE_COLOR`, `COLORTERM` and `TERM`
+    pub fn detect() -> Self {
+        Self::detect_from(|name| std::env::var(name).ok())
+    }
+
+
+    /// Detects the color depth from environment variables looked up by `var`
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let depth = ColorDepth::detect_from(|name| (name == "TERM").then(|| "xterm-256color".to_string()));
+    /// assert_eq!(depth, ColorDepth::Ansi256);
+    /// ```
+    pub fn detect_from<F: Fn(&str) -> Option<String>>(var: F) -> Self {
+        let detected = Self::detect_terminal(&var);
+
+        // FORCE_COLOR wins over NO_COLOR, and takes a level like chalk and supports-color do
+        if let Some(force) = var("FORCE_COLOR") {
+            return match force.trim().to_lowercase().as_str() {
+                "0" | "false" => ColorDepth::NoColor,
+                "2" => ColorDepth::Ansi256,
+                "3" => ColorDepth::TrueColor,
+                _ => detected.max(ColorDepth::Ansi16),
+            };
+        }
+        if var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return ColorDepth::NoColor;
+        }
+        detected
+    }
+
+
+    fn detect_terminal<F: Fn(&str) -> Option<String>>(var: &F) -> Self {
+        let colorterm = var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" || var("WT_SESSION").is_some() {
+            return ColorDepth::TrueColor;
+        }
+
+        match var("TERM").map(|t| t.to_lowercase()) {
+            Some(term) if term == "dumb" => ColorDepth::NoColor,
+            Some(term) if term.contains("truecolor") || term.contains("24bit") || term.contains("direct") => ColorDepth::TrueColor,
+            Some(term) if term.contains("256") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+
+
+    /// Maps a color to the nearest one the terminal can show, or `None` if it can't show colors
+    pub(crate) fn reduce(&self, color: Color) -> Option<Color> {
+        match (self, color) {
+            (ColorDepth::NoColor, _) => None,
+            (ColorDepth::Ansi256, Color::RGB(r, g, b)) => Some(Color::Indexed(nearest_indexed(r, g, b))),
+            (ColorDepth::Ansi16, Color::Indexed(index)) if index < 16 => Some(ansi::named_color(index as u16)),
+            (ColorDepth::Ansi16, Color::RGB(..) | Color::Indexed(_)) => Some(nearest_named(color.to_rgb())),
+            (_, color) => Some(color),
+        }
+    }
+
+
+    /// Reduces every color of a style, dropping the ones the terminal can't show
+    ///
+    /// Underline colors need at least 256 colors, and underline shapes other than a single line need truecolor,
+    /// as terminals that lack them tend to misread the sequences.
+    pub(crate) fn reduce_style(&self, style: Style) -> Style {
+        let underline = match style.underline() {
+            Some(shape) if *self == ColorDepth::TrueColor => Some(shape),
+            Some(_) => Some(UnderlineStyle::Single),
+            None => None,
+        };
+        Style {
+            fg: style.fg.and_then(|c| self.reduce(c)),
+            bg: style.bg.and_then(|c| self.reduce(c)),
+            underline_color: style.underline_color.filter(|_| *self >= ColorDepth::Ansi256).and_then(|c| self.reduce(c)),
+            underline_style: underline,
+            ..style
+        }
+    }
+}
+
+/// How far apart two colors look, weighting the channels like the eye does ("redmean")
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let mean = (r1 as i32 + r2 as i32) / 2;
+    let (dr, dg, db) = (r1 as i32 - r2 as i32, g1 as i32 - g2 as i32, b1 as i32 - b2 as i32);
+    ((((512 + mean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - mean) * db * db) >> 8)) as u32
+}
+
+/// The nearest entry of the 6x6x6 color cube or the grayscale ramp of the 256 color palette
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    (16..=255u8)
+        .min_by_key(|&i| distance((r, g, b), Color::Indexed(i).to_rgb()))
+        .unwrap_or(16)
+}
+
+fn nearest_named(rgb: (u8, u8, u8)) -> Color {
+    (0..16u16)
+        .map(ansi::named_color)
+        .min_by_key(|c| distance(rgb, c.to_rgb()))
+        .unwrap_or(Color::White)
+}
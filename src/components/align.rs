@@ -0,0 +1,72 @@
+use super::text::Text;
+use super::wrap::{is_space, items_width, Item};
+
+/// * How the lines of a `Text` are placed within a width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretches the gaps between words so lines fill the whole width,
+    /// except for the last line of every paragraph
+    Justify,
+}
+
+/// Spreads `extra` spaces over the gaps between the words of a line, the leftmost gaps getting any remainder
+fn justify(line: Vec<Item<'_>>, extra: usize) -> Vec<Item<'_>> {
+    let start = line.iter().position(|i| !is_space(i)).unwrap_or(line.len());
+    let gaps = (start + 1..line.len())
+        .filter(|&i| is_space(&line[i]) && !is_space(&line[i - 1]))
+        .collect::<Vec<_>>();
+    if gaps.is_empty() {
+        return line;
+    }
+
+    let mut justified = Vec::with_capacity(line.len() + extra);
+    let mut gap = 0;
+    for (i, item) in line.into_iter().enumerate() {
+        if gaps.get(gap) == Some(&i) {
+            let spaces = extra / gaps.len() + usize::from(gap < extra % gaps.len());
+            justified.extend(std::iter::repeat_n((" ", item.1), spaces));
+            gap += 1;
+        }
+        justified.push(item);
+    }
+    justified
+}
+
+impl Text {
+
+    /// * Aligns every line within `width` columns, keeping the styles of every span
+    ///
+    /// Lines wider than `width` are left as they are. Use `Text::width` to align lines against the widest one.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text: Text = "Settings\nSound on".into();
+    /// assert_eq!(text.align(Alignment::Center, 12).render_plain(), "  Settings\n  Sound on");
+    /// assert_eq!(text.align(Alignment::Right, 10).render_plain(), "  Settings\n  Sound on");
+    ///
+    /// let text: Text = "a few words\nto fill\nend".into();
+    /// assert_eq!(text.align(Alignment::Justify, 12).render_plain(), "a  few words\nto      fill\nend");
+    /// ```
+    pub fn align(&self, alignment: Alignment, width: usize) -> Text {
+        let lines = self.lines();
+        let count = lines.len();
+        let mut aligned = Vec::with_capacity(count);
+
+        for (i, line) in lines.iter().enumerate() {
+            let extra = width.saturating_sub(items_width(line));
+            let line = line.clone();
+            let ends_paragraph = i + 1 == count || lines[i + 1].is_empty();
+            aligned.push(match alignment {
+                Alignment::Left => (0, line),
+                Alignment::Center => (extra / 2, line),
+                Alignment::Right => (extra, line),
+                Alignment::Justify if ends_paragraph => (0, line),
+                Alignment::Justify => (0, justify(line, extra)),
+            });
+        }
+        self.join_lines(aligned)
+    }
+}
@@ -1,24 +1,35 @@
-use super::Component;
+use super::{Component, Gradient, Text};
 use figlet_rs::FIGfont;
 
 pub struct AsciiArt {
-    text: String
+    text: String,
+    gradient: Option<Gradient>
 }
 
 impl AsciiArt {
     
     pub fn new(text: &str) -> Self {
         Self {
-            text: text.to_string()
+            text: text.to_string(),
+            gradient: None
         }
     }
+
+
+    /// Colors the letters with a gradient
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = Some(gradient); self
+    }
 }
 
 impl Component for AsciiArt {
     
     fn render(&self) -> String {
         let standard_font = FIGfont::standard().unwrap();
-        let figure = standard_font.convert(&self.text);
-        figure.unwrap().to_string()
+        let figure = standard_font.convert(&self.text).unwrap().to_string();
+        match &self.gradient {
+            Some(gradient) => Text::from(figure).gradient(gradient).render(),
+            None => figure
+        }
     }
 }
@@ -0,0 +1,165 @@
+use std::ops::RangeInclusive;
+use super::syntax::{tokenize, Language, TokenKind};
+use super::{Component, Modifier, Style, Text};
+use crate::theme::Theme;
+
+const TAB_WIDTH: usize = 4;
+
+/// * Code - source highlighted with the colors of the theme
+///
+/// Tokens take the theme's roles: keywords and config keys are `primary`, strings `success`, numbers and
+/// constants `warning`, comments `muted`, types `secondary` and function calls `accent`.
+/// ```
+/// # use tuitui::prelude::*;
+/// let code = Code::new("let answer = 42; // the answer", Language::Rust)
+///     .with_line_numbers(true)
+///     .with_highlight(1..=1);
+///
+/// let text = code.to_text(&Theme::dark());
+/// assert_eq!(text.render_plain(), "1 │ let answer = 42; // the answer");
+/// ```
+///
+/// Long lines can be cut to a width and scrolled through sideways:
+/// ```
+/// # use tuitui::prelude::*;
+/// let code = Code::new("[package]\nname = \"tuitui\"", Language::Toml).with_width(6).with_scroll(3);
+/// assert_eq!(code.to_text(&Theme::dark()).render_plain(), "ckage]\ne = \"t");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Code {
+    source: String,
+    language: Language,
+    line_numbers: bool,
+    first_line: usize,
+    highlighted: Vec<RangeInclusive<usize>>,
+    scroll: usize,
+    width: Option<usize>,
+}
+
+impl Code {
+
+    /// Tabs in `source` are expanded to 4 columns
+    pub fn new(source: &str, language: Language) -> Self {
+        Self {
+            source: source.replace("\r\n", "\n").replace('\t', &" ".repeat(TAB_WIDTH)),
+            language,
+            line_numbers: false,
+            first_line: 1,
+            highlighted: Vec::new(),
+            scroll: 0,
+            width: None,
+        }
+    }
+
+
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers; self
+    }
+
+
+    /// The number shown for the first line, for snippets taken out of a bigger file
+    pub fn with_first_line(mut self, number: usize) -> Self {
+        self.first_line = number; self
+    }
+
+
+    /// Marks a range of lines with the theme's `surface` color. Lines are numbered like the line numbers
+    pub fn with_highlight(mut self, lines: RangeInclusive<usize>) -> Self {
+        self.highlighted.push(lines); self
+    }
+
+
+    /// Skips that many columns at the start of every line
+    pub fn with_scroll(mut self, columns: usize) -> Self {
+        self.scroll = columns; self
+    }
+
+
+    /// Cuts every line to that many columns, not counting the line numbers
+    pub fn with_width(mut self, columns: usize) -> Self {
+        self.width = Some(columns); self
+    }
+
+
+    pub fn set_scroll(&mut self, columns: usize) -> &mut Self {
+        self.scroll = columns;
+        self
+    }
+
+
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+
+    /// The whole source as spans colored by `theme`, before line numbers, highlights and scrolling
+    pub fn highlighted(&self, theme: &Theme) -> Text {
+        tokenize(&self.source, self.language)
+            .into_iter()
+            .map(|(kind, content)| {
+                let mut text = Text::from(content);
+                if kind != TokenKind::Plain {
+                    text.patch_style(kind.style(theme));
+                }
+                text
+            })
+            .fold(Text::new(), |text, token| text + token)
+    }
+
+
+    /// * Lays the code out like it's rendered: with line numbers, highlighted lines and the visible columns only
+    pub fn to_text(&self, theme: &Theme) -> Text {
+        let lines = self.highlighted(theme).split_lines();
+        let last = self.first_line + lines.len().saturating_sub(1);
+        let digits = last.to_string().len();
+        let visible = self.width.unwrap_or_else(|| {
+            lines.iter().map(Text::width).max().unwrap_or(0).saturating_sub(self.scroll)
+        });
+
+        let mut text = Text::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let number = self.first_line + i;
+            let marked = self.highlighted.iter().any(|r| r.contains(&number));
+            if i > 0 {
+                text.span("\n");
+            }
+            if self.line_numbers {
+                let style = if marked {
+                    Style::new().with_fg(theme.primary).with_modifier(Modifier::Bold)
+                } else {
+                    Style::new().with_fg(theme.muted)
+                };
+                text.span(&format!("{:>digits$} ", number)).style(style);
+                text.span("│ ").style(Style { fg: theme.border, ..Style::new() });
+            }
+
+            let mut visible_line = line.slice(self.scroll..self.scroll.saturating_add(visible));
+            if marked {
+                let padding = visible.saturating_sub(visible_line.width());
+                if padding > 0 {
+                    visible_line.span(&" ".repeat(padding));
+                }
+                visible_line.patch_style(Style::new().with_bg(theme.surface));
+            }
+            text += visible_line;
+        }
+        text
+    }
+}
+
+impl Component for Code {
+
+    fn render(&self) -> String {
+        self.to_text(&Theme::default()).render()
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        self.to_text(theme).render()
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::str::FromStr;
+use super::text::Color;
+use crate::markup;
+
+/// * Describes why a string couldn't be parsed as a `Color`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorParseError {
+    pub details: String,
+}
+
+impl fmt::Display for ColorParseError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// Parses the same color names, `#rrggbb`, `#rgb`, `rgb(r, g, b)` and `color(n)` forms as markup tags
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        markup::parse_color(s.trim().as_bytes()).ok_or_else(|| ColorParseError {
+            details: format!("`{}` isn't a color name, hex color, rgb(r, g, b) or color(n)", s),
+        })
+    }
+}
+
+/// Clamps a fraction to `0.0..=1.0` and scales it to a channel
+fn channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl Color {
+
+    /// * Parses a `#rrggbb` or `#rgb` hex color, with or without the `#`
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// assert_eq!(Color::from_hex("#7a62a8"), Ok(Color::RGB(122, 98, 168)));
+    /// assert_eq!(Color::from_hex("fff"), Ok(Color::RGB(255, 255, 255)));
+    /// assert!(Color::from_hex("#7a62a").is_err());
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Color, ColorParseError> {
+        let digits = hex.trim().strip_prefix('#').unwrap_or(hex.trim());
+        markup::parse_hex_color(digits.as_bytes()).ok_or_else(|| ColorParseError {
+            details: format!("`{}` isn't a hex color like #rrggbb or #rgb", hex),
+        })
+    }
+
+
+    /// * Builds a color from a hue in degrees, and a saturation and lightness between 0 and 1
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        let (s, l) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_chroma(hue, chroma, l - chroma / 2.0)
+    }
+
+
+    /// * Builds a color from a hue in degrees, and a saturation and value between 0 and 1
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let (s, v) = (saturation.clamp(0.0, 1.0), value.clamp(0.0, 1.0));
+        let chroma = v * s;
+        Self::from_chroma(hue, chroma, v - chroma)
+    }
+
+
+    fn from_chroma(hue: f32, chroma: f32, lightness: f32) -> Color {
+        let h = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        Color::RGB(channel(r + lightness), channel(g + lightness), channel(b + lightness))
+    }
+
+
+    /// The hue in degrees, and the saturation and lightness between 0 and 1
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.to_rgb();
+        let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (hue, saturation, lightness)
+    }
+
+
+    /// * Raises the lightness by `amount` (between 0 and 1), keeping the hue
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let brand = Color::from_hex("#7a62a8").unwrap();
+    /// let hover = brand.lighten(0.1);
+    /// let pressed = brand.darken(0.1);
+    /// assert!(hover.contrast_ratio(Color::Black) > brand.contrast_ratio(Color::Black));
+    /// assert!(pressed.contrast_ratio(Color::Black) < brand.contrast_ratio(Color::Black));
+    /// ```
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s, l + amount)
+    }
+
+
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+
+    /// * Blends towards `other`: 0 keeps this color, 1 gives `other`
+    pub fn mix(&self, other: Color, amount: f32) -> Color {
+        let t = amount.clamp(0.0, 1.0);
+        let (r1, g1, b1) = self.to_rgb();
+        let (r2, g2, b2) = other.to_rgb();
+        let blend = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color::RGB(blend(r1, r2), blend(g1, g2), blend(b1, b2))
+    }
+
+
+    /// The relative luminance as defined by WCAG 2, from 0 for black to 1 for white
+    pub fn luminance(&self) -> f32 {
+        let (r, g, b) = self.to_rgb();
+        let linear = |c: u8| {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+
+
+    /// * The WCAG 2 contrast ratio between two colors, from 1 to 21
+    ///
+    /// Body text should have a ratio of at least 4.5 against its background, and large text at least 3.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let ratio = Color::RGB(0, 0, 0).contrast_ratio(Color::RGB(255, 255, 255));
+    /// assert!((ratio - 21.0).abs() < 0.01);
+    /// ```
+    pub fn contrast_ratio(&self, other: Color) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
@@ -0,0 +1,77 @@
+use super::text::Text;
+use super::wrap::{items_width, Item};
+use crate::width;
+
+/// * Where `Text::ellipsize` cuts text that doesn't fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ellipsis {
+    /// `a long ti…`
+    #[default]
+    End,
+    /// `…ong title`
+    Start,
+    /// `a lo…title`, which keeps both ends of file paths
+    Middle,
+}
+
+const ELLIPSIS: &str = "…";
+
+/// The longest run of items from the front of `items` that fits in `columns`
+fn prefix<'a>(items: &[Item<'a>], columns: usize) -> usize {
+    let mut used = 0;
+    items
+        .iter()
+        .take_while(|(g, _)| {
+            used += width::grapheme_width(g);
+            used <= columns
+        })
+        .count()
+}
+
+fn ellipsize_line(line: Vec<Item<'_>>, columns: usize, position: Ellipsis) -> Vec<Item<'_>> {
+    if items_width(&line) <= columns {
+        return line;
+    }
+    if columns == 0 {
+        return Vec::new();
+    }
+
+    // The ellipsis takes the style of the first item it replaces
+    let available = columns - 1;
+    let (head, tail) = match position {
+        Ellipsis::End => (available, 0),
+        Ellipsis::Start => (0, available),
+        Ellipsis::Middle => (available - available / 2, available / 2),
+    };
+    let reversed = line.iter().rev().copied().collect::<Vec<_>>();
+    let head = prefix(&line, head);
+    let tail = prefix(&reversed, tail);
+    let span = if head < line.len() { line[head].1 } else { line[line.len() - 1].1 };
+
+    let mut ellipsized = line[..head].to_vec();
+    ellipsized.push((ELLIPSIS, span));
+    ellipsized.extend_from_slice(&line[line.len() - tail..]);
+    ellipsized
+}
+
+impl Text {
+
+    /// * Cuts every line longer than `width` columns down to size, replacing what's left out with `…`
+    ///
+    /// Styles are kept, and the ellipsis is drawn with the style of the text it replaces.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let path: Text = "/home/tux/projects/tuitui/src/main.rs".into();
+    /// assert_eq!(path.ellipsize(12, Ellipsis::End).render_plain(), "/home/tux/p…");
+    /// assert_eq!(path.ellipsize(12, Ellipsis::Start).render_plain(), "…src/main.rs");
+    /// assert_eq!(path.ellipsize(12, Ellipsis::Middle).render_plain(), "/home/…in.rs");
+    /// ```
+    pub fn ellipsize(&self, width: usize, position: Ellipsis) -> Text {
+        let lines = self
+            .lines()
+            .into_iter()
+            .map(|line| (0, ellipsize_line(line, width, position)))
+            .collect();
+        self.join_lines(lines)
+    }
+}
@@ -0,0 +1,217 @@
+use super::text::{Color, Text, TextSpan};
+use crate::width;
+
+/// * Which way a `Gradient` runs across a `Text`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientDirection {
+    /// From the first column to the last
+    #[default]
+    Horizontal,
+    /// From the first line to the last
+    Vertical,
+    /// From the top left corner to the bottom right one
+    Diagonal,
+}
+
+/// * How colors between two stops are blended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Straight blending of the red, green and blue channels
+    Rgb,
+    /// Blending in the Oklab color space, which keeps the brightness even and avoids muddy midpoints
+    #[default]
+    Perceptual,
+}
+
+/// * A linear gradient through any number of color stops
+///
+/// Gradients are made of `Color::RGB`s, so on terminals without truecolor the renderer maps every
+/// step to the nearest color the terminal has (see `ColorDepth`).
+/// ```
+/// # use tuitui::prelude::*;
+/// let sunset = Gradient::new(&[Color::from_hex("#ff7e5f").unwrap(), Color::from_hex("#feb47b").unwrap()]);
+/// let title = Text::from("Tuitui").gradient(&sunset);
+///
+/// let red_to_blue = Gradient::new(&[Color::RGB(255, 0, 0), Color::RGB(0, 0, 255)])
+///     .with_interpolation(Interpolation::Rgb);
+/// assert_eq!(red_to_blue.color_at(0.0), Color::RGB(255, 0, 0));
+/// assert_eq!(red_to_blue.color_at(0.5), Color::RGB(128, 0, 128));
+/// assert_eq!(red_to_blue.color_at(1.0), Color::RGB(0, 0, 255));
+///
+/// // Every column gets its own color, running from the first stop to the last
+/// let painted = Text::from("abc").gradient(&red_to_blue);
+/// let colors: Vec<_> = painted.spans().iter().map(|s| s.get_style().fg).collect();
+/// assert_eq!(colors, [Some(Color::RGB(255, 0, 0)), Some(Color::RGB(128, 0, 128)), Some(Color::RGB(0, 0, 255))]);
+///
+/// let vertical = Gradient::new(&[Color::Blue, Color::Cyan]).with_direction(GradientDirection::Vertical);
+/// let art = AsciiArt::new("Hi").with_gradient(vertical);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    /// Positions between 0 and 1 with their colors, sorted by position
+    stops: Vec<(f32, Color)>,
+    pub direction: GradientDirection,
+    pub interpolation: Interpolation,
+}
+
+impl Gradient {
+
+    /// Spreads the colors evenly from start to end
+    pub fn new(colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self {
+            stops: colors.iter().enumerate().map(|(i, c)| (i as f32 / last, *c)).collect(),
+            direction: GradientDirection::default(),
+            interpolation: Interpolation::default(),
+        }
+    }
+
+
+    /// Adds a color at a position between 0 and 1
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        let position = position.clamp(0.0, 1.0);
+        let index = self.stops.partition_point(|(p, _)| *p <= position);
+        self.stops.insert(index, (position, color));
+        self
+    }
+
+
+    pub fn with_direction(mut self, direction: GradientDirection) -> Self {
+        self.direction = direction; self
+    }
+
+
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation; self
+    }
+
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+
+    /// * The color at a position between 0 and 1
+    pub fn color_at(&self, position: f32) -> Color {
+        let t = position.clamp(0.0, 1.0);
+        let Some(&(first_position, first)) = self.stops.first() else {
+            return Color::White;
+        };
+        if t <= first_position {
+            return first;
+        }
+
+        for pair in self.stops.windows(2) {
+            let ((start, from), (end, to)) = (pair[0], pair[1]);
+            if t <= end {
+                let amount = if end > start { (t - start) / (end - start) } else { 1.0 };
+                return match self.interpolation {
+                    Interpolation::Rgb => from.mix(to, amount),
+                    Interpolation::Perceptual => mix_oklab(from, to, amount),
+                };
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+
+
+    /// Colors every grapheme cluster of `text`, as its foreground or its background
+    fn paint(&self, text: &Text, background: bool) -> Text {
+        let lines = text.lines();
+        let height = lines.len();
+        let width = lines.iter().map(|l| super::wrap::items_width(l)).max().unwrap_or(0);
+        let fraction = |value: usize, total: usize| if total > 1 { value as f32 / (total - 1) as f32 } else { 0.0 };
+
+        let mut painted = Text::new();
+        for (y, line) in lines.iter().enumerate() {
+            if y > 0 {
+                painted.span("\n");
+            }
+            let mut x = 0;
+            let mut current: Option<TextSpan> = None;
+            for item in line {
+                let position = match self.direction {
+                    GradientDirection::Horizontal => fraction(x, width),
+                    GradientDirection::Vertical => fraction(y, height),
+                    GradientDirection::Diagonal => (fraction(x, width) + fraction(y, height)) / 2.0,
+                };
+                x += width::grapheme_width(item.0);
+                let color = self.color_at(position);
+
+                let mut span = text.spans[item.1].clone();
+                span.contents(item.0);
+                if background { span.bg_color(color) } else { span.color(color) };
+
+                // Neighbours that ended up with the same color and style share a span
+                match &mut current {
+                    Some(previous) if previous.same_style(&span) => {
+                        previous.contents(&format!("{}{}", previous.render_plain(), item.0));
+                    }
+                    _ => {
+                        if let Some(previous) = current.take() {
+                            painted.span_from(previous);
+                        }
+                        current = Some(span);
+                    }
+                }
+            }
+            if let Some(span) = current {
+                painted.span_from(span);
+            }
+        }
+        painted
+    }
+}
+
+impl Text {
+
+    /// * Colors the text with a gradient, keeping every other style
+    pub fn gradient(&self, gradient: &Gradient) -> Text {
+        gradient.paint(self, false)
+    }
+
+
+    /// * Fills the background of the text with a gradient
+    pub fn gradient_background(&self, gradient: &Gradient) -> Text {
+        gradient.paint(self, true)
+    }
+}
+
+fn to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn from_linear(c: f64) -> u8 {
+    let c = if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.max(0.0).powf(1.0 / 2.4) - 0.055 };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn to_oklab(color: Color) -> [f64; 3] {
+    let (r, g, b) = color.to_rgb();
+    let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+fn from_oklab([l, a, b]: [f64; 3]) -> Color {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    Color::RGB(
+        from_linear(4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_),
+        from_linear(-1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_),
+        from_linear(-0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_),
+    )
+}
+
+fn mix_oklab(from: Color, to: Color, amount: f32) -> Color {
+    let (a, b) = (to_oklab(from), to_oklab(to));
+    from_oklab([0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * amount as f64))
+}
@@ -0,0 +1,440 @@
+use std::mem;
+use pulldown_cmark::{Alignment as ColumnAlignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use super::{AsciiArt, Component, Modifier, Separator, Style, Text, Widget};
+#[cfg(feature = "code")]
+use super::{Code, Language};
+use crate::theme::Theme;
+
+/// The width of horizontal rules when the `Markdown` isn't given one
+const RULE_WIDTH: usize = 40;
+
+/// * Markdown - CommonMark rendered with the colors of the theme
+///
+/// Headings take the theme's heading style, code blocks are drawn in widgets (and highlighted with the `code`
+/// feature), rules become separators and links become hyperlinks. Tables and strikethrough from GitHub's
+/// flavour are understood too.
+/// ```
+/// # use tuitui::prelude::*;
+/// let help = Markdown::new("# Help\n\nPress **q** to quit.\n\n- `j` goes down\n- `k` goes up");
+/// assert_eq!(
+///     help.to_text(&Theme::dark()).render_plain(),
+///     "Help\n\nPress q to quit.\n\n• j goes down\n• k goes up",
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Markdown {
+    source: String,
+    width: Option<usize>,
+    ascii_headings: bool,
+    inline_links: bool,
+}
+
+impl Markdown {
+
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            width: None,
+            ascii_headings: false,
+            inline_links: false,
+        }
+    }
+
+
+    /// Wraps paragraphs to that many columns and draws rules across them
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width); self
+    }
+
+
+    /// Draws top level headings as `AsciiArt`
+    pub fn with_ascii_headings(mut self, ascii_headings: bool) -> Self {
+        self.ascii_headings = ascii_headings; self
+    }
+
+
+    /// Writes the target of every link after it, for terminals without hyperlinks (see `Text::inline_links`)
+    pub fn with_inline_links(mut self, inline_links: bool) -> Self {
+        self.inline_links = inline_links; self
+    }
+
+
+    pub fn to_text(&self, theme: &Theme) -> Text {
+        let mut renderer = Renderer {
+            theme,
+            width: self.width,
+            ascii_headings: self.ascii_headings,
+            inline_links: self.inline_links,
+            output: Text::new(),
+            containers: Vec::new(),
+            lists: Vec::new(),
+            inline: Text::new(),
+            styles: Vec::new(),
+            links: Vec::new(),
+            code: None,
+            table: None,
+            gap: false,
+        };
+        let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+        Parser::new_ext(&self.source, options).for_each(|event| renderer.event(event));
+        renderer.flush();
+        renderer.output
+    }
+}
+
+impl Component for Markdown {
+
+    fn render(&self) -> String {
+        self.to_text(&Theme::default()).render()
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        self.to_text(theme).render()
+    }
+}
+
+/// A block the lines of its contents are drawn in
+enum Container {
+    Quote,
+    /// A list item, indented by the width of its marker. The marker is drawn on its first line only
+    Item { indent: usize, marker: Option<Text> },
+}
+
+struct Table {
+    alignments: Vec<ColumnAlignment>,
+    rows: Vec<Vec<Text>>,
+    head_rows: usize,
+}
+
+struct Renderer<'t> {
+    theme: &'t Theme,
+    width: Option<usize>,
+    ascii_headings: bool,
+    inline_links: bool,
+    output: Text,
+    containers: Vec<Container>,
+    /// The next number of every open list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    /// The paragraph, heading or table cell being built
+    inline: Text,
+    styles: Vec<Style>,
+    links: Vec<String>,
+    /// The language and source of the code block being read
+    code: Option<(String, String)>,
+    table: Option<Table>,
+    /// Whether a blank line goes before the next block
+    gap: bool,
+}
+
+impl Renderer<'_> {
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code {
+                Some((_, source)) => source.push_str(&text),
+                None => self.push(&text, Style::new()),
+            },
+            Event::Code(code) => {
+                let style = Style::new().with_fg(self.theme.accent).with_bg(self.theme.surface);
+                self.push(&code, style);
+            }
+            Event::SoftBreak => self.push(" ", Style::new()),
+            Event::HardBreak => self.push("\n", Style::new()),
+            Event::TaskListMarker(done) => self.push(if done { "[x] " } else { "[ ] " }, Style::new()),
+            Event::Rule => {
+                self.flush();
+                let width = self.width.unwrap_or(RULE_WIDTH).saturating_sub(self.prefix_width()).max(1);
+                let rule = Separator::fill("─".into(), width).render_themed(self.theme);
+                self.emit(Text::from_ansi(&rule));
+            }
+            _ => {}
+        }
+    }
+
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { .. } => self.flush(),
+            Tag::Table(alignments) => {
+                self.flush();
+                self.table = Some(Table { alignments, rows: Vec::new(), head_rows: 0 });
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.separate();
+                self.containers.push(Container::Quote);
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or("").to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::List(start) => {
+                self.flush();
+                if self.lists.is_empty() {
+                    self.separate();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => format!("{} ", ["•", "◦", "▪"][depth % 3]),
+                };
+                let mut text = Text::new();
+                text.span(&marker).color(self.theme.primary);
+                self.containers.push(Container::Item { indent: text.width(), marker: Some(text) });
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::Emphasis => self.styles.push(Style::from(Modifier::Italic)),
+            Tag::Strong => self.styles.push(Style::from(Modifier::Bold)),
+            Tag::Strikethrough => self.styles.push(Style::from(Modifier::Strikethrough)),
+            Tag::Link { dest_url, .. } => {
+                self.styles.push(Style::new().with_fg(self.theme.accent).with_modifier(Modifier::Underlined));
+                self.links.push(dest_url.to_string());
+            }
+            Tag::Image { dest_url, .. } => {
+                self.styles.push(Style::new().with_fg(self.theme.muted).with_modifier(Modifier::Italic));
+                self.links.push(dest_url.to_string());
+                self.push("[", Style::new());
+            }
+            _ => {}
+        }
+    }
+
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.flush(),
+            TagEnd::Heading(level) => self.heading(level),
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.containers.pop();
+                self.gap = true;
+            }
+            TagEnd::CodeBlock => self.code_block(),
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                self.gap = self.gap || self.lists.is_empty();
+            }
+            TagEnd::Item => {
+                self.flush();
+                // An empty item still shows its marker
+                if matches!(self.containers.last(), Some(Container::Item { marker: Some(_), .. })) {
+                    self.emit(Text::new());
+                }
+                self.containers.pop();
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.head_rows = table.rows.len();
+                }
+            }
+            TagEnd::TableCell => {
+                let cell = mem::take(&mut self.inline);
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(cell);
+                }
+            }
+            TagEnd::Table => self.table(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+                self.close_link();
+            }
+            TagEnd::Image => {
+                self.push("]", Style::new());
+                self.styles.pop();
+                self.close_link();
+            }
+            _ => {}
+        }
+    }
+
+
+    /// Ends the innermost link, writing its target after it as plain text when hyperlinks aren't shown
+    fn close_link(&mut self) {
+        let url = self.links.pop();
+        if let Some(url) = url.filter(|_| self.inline_links) {
+            self.inline.span(&format!(" ({})", url));
+        }
+    }
+
+
+    /// Adds inline content with the styles of the emphasis and links around it
+    fn push(&mut self, content: &str, style: Style) {
+        let style = self.styles.iter().fold(Style::new(), |all, s| all.patch(*s)).patch(style);
+        let span = self.inline.span(content);
+        span.style(style);
+        if let Some(url) = self.links.last() {
+            span.link(url);
+        }
+    }
+
+
+    /// The number of columns the quotes and list items around a block take
+    fn prefix_width(&self) -> usize {
+        self.containers
+            .iter()
+            .map(|c| match c {
+                Container::Quote => 2,
+                Container::Item { indent, .. } => *indent,
+            })
+            .sum()
+    }
+
+
+    /// What goes in front of a line of the current block: quote bars, and list markers or indentation
+    fn prefix(&mut self) -> Text {
+        let mut prefix = Text::new();
+        for container in &mut self.containers {
+            match container {
+                Container::Quote => prefix += Text::from("│ ").inherit(Style { fg: self.theme.border, ..Style::new() }),
+                Container::Item { indent, marker } => prefix += marker.take().unwrap_or_else(|| " ".repeat(*indent).into()),
+            }
+        }
+        prefix
+    }
+
+
+    /// Puts the blank line between two blocks, if one is due
+    fn separate(&mut self) {
+        if self.gap && !self.output.spans().is_empty() {
+            let quotes = self.containers.iter().filter(|c| matches!(c, Container::Quote)).count();
+            self.output += "\n";
+            self.output += Text::from("│ ".repeat(quotes).trim_end()).inherit(Style { fg: self.theme.border, ..Style::new() });
+        }
+        self.gap = false;
+    }
+
+
+    /// Adds a finished block to the output, inside the quotes and list items it belongs to
+    fn emit(&mut self, block: Text) {
+        // Items follow each other without blank lines, even the first block of one
+        if !self.containers.iter().any(|c| matches!(c, Container::Item { marker: Some(_), .. })) {
+            self.separate();
+        }
+        for line in block.split_lines() {
+            if !self.output.spans().is_empty() {
+                self.output += "\n";
+            }
+            let prefix = self.prefix();
+            self.output += prefix;
+            self.output += line;
+        }
+        self.gap = self.lists.is_empty();
+    }
+
+
+    /// Emits the paragraph being built, wrapped to the width left by the containers
+    fn flush(&mut self) {
+        if self.inline.spans().is_empty() {
+            return;
+        }
+        let inline = mem::take(&mut self.inline);
+        match self.width {
+            Some(width) => self.emit(inline.wrap(width.saturating_sub(self.prefix_width()).max(1))),
+            None => self.emit(inline),
+        }
+    }
+
+
+    fn heading(&mut self, level: HeadingLevel) {
+        let text = mem::take(&mut self.inline);
+        let theme = self.theme;
+        if level == HeadingLevel::H1 && self.ascii_headings {
+            let art = AsciiArt::new(&text.render_plain()).render();
+            self.emit(Text::from_ansi(art.trim_end()).inherit(Style::new().with_fg(theme.primary)));
+            return;
+        }
+        let style = match level {
+            HeadingLevel::H1 => theme.heading_style,
+            HeadingLevel::H2 => Style { fg: theme.heading_style.fg.map(|_| theme.secondary), ..theme.heading_style },
+            _ => Style::from(Modifier::Bold),
+        };
+        self.emit(text.inherit(style));
+    }
+
+
+    fn code_block(&mut self) {
+        let Some((language, source)) = self.code.take() else {
+            return;
+        };
+        let source = source.strip_suffix('\n').unwrap_or(&source);
+
+        #[cfg(feature = "code")]
+        let contents = Code::new(source, Language::from_name(&language)).to_text(self.theme);
+        #[cfg(not(feature = "code"))]
+        let contents = Text::from(source.replace('\t', "    "));
+
+        let mut width = contents.width() + 2;
+        let mut widget = Widget::new().with_height(2);
+        if !language.is_empty() {
+            width = width.max(language.len() + 6);
+            widget = widget.with_title(Text::from(language.as_str()));
+        }
+        let widget = widget.with_contents(contents).with_width(width as u16).build();
+        self.emit(Text::from_ansi(&widget.render_themed(self.theme)));
+    }
+
+
+    fn table(&mut self) {
+        let Some(table) = self.table.take() else {
+            return;
+        };
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths = (0..columns)
+            .map(|c| table.rows.iter().filter_map(|r| r.get(c)).map(Text::width).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+        let border = Style { fg: self.theme.border, ..Style::new() };
+        let heading = self.theme.heading_style;
+
+        let mut text = Text::new();
+        for (i, row) in table.rows.iter().enumerate() {
+            if i > 0 {
+                text += "\n";
+            }
+            for (c, width) in widths.iter().enumerate() {
+                if c > 0 {
+                    text.span(" │ ").style(border);
+                }
+                let cell = row.get(c).cloned().unwrap_or_default();
+                let extra = width - cell.width();
+                let before = match table.alignments.get(c) {
+                    Some(ColumnAlignment::Right) => extra,
+                    Some(ColumnAlignment::Center) => extra / 2,
+                    _ => 0,
+                };
+                text.span(&" ".repeat(before));
+                text += if i < table.head_rows { cell.inherit(heading) } else { cell };
+                if c + 1 < widths.len() {
+                    text.span(&" ".repeat(extra - before));
+                }
+            }
+            if i + 1 == table.head_rows {
+                let rule = widths.iter().map(|w| "─".repeat(*w)).collect::<Vec<_>>().join("─┼─");
+                text += "\n";
+                text.span(&rule).style(border);
+            }
+        }
+        self.emit(text);
+    }
+}
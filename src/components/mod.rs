@@ -2,16 +2,48 @@ pub mod separator;
 pub mod widget;
 pub mod ascii_art;
 pub mod text;
+mod color;
+pub mod wrap;
+pub mod align;
+pub mod ellipsis;
+pub mod gradient;
+pub mod style;
+pub mod slice;
+pub mod search;
+pub mod text_area;
+pub mod password;
+#[cfg(feature = "code")]
+pub mod syntax;
+#[cfg(feature = "code")]
+pub mod code;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 
 pub use separator::Separator;
 pub use widget::*;
 pub use ascii_art::AsciiArt;
 pub use text::Text;
+use crate::theme::Theme;
+pub use color::ColorParseError;
+pub use wrap::{WrapMode, WrapOptions};
+pub use align::Alignment;
+pub use ellipsis::Ellipsis;
+pub use gradient::{Gradient, GradientDirection, Interpolation};
+pub use style::{Modifier, Modifiers, Style, UnderlineStyle};
+pub use text_area::TextArea;
+pub use password::PasswordInput;
+#[cfg(feature = "code")]
+pub use syntax::Language;
+#[cfg(feature = "code")]
+pub use code::Code;
+#[cfg(feature = "markdown")]
+pub use markdown::Markdown;
 
 /// * Defines the way an object is rendered
 /// 
 /// You can easily `impl` this with your structs:
 /// ```no_run
+/// # use tuitui::prelude::*;
 /// struct MyComponent;
 /// 
 /// impl Component for MyComponent {
@@ -22,4 +54,9 @@ pub use text::Text;
 /// ```
 pub trait Component {
     fn render(&self) -> String;
+
+    /// Renders with the colors and styles of a theme. Components that don't use a theme can leave this out
+    fn render_themed(&self, _theme: &Theme) -> String {
+        self.render()
+    }
 }
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::iter;
+use std::ops::Range;
+use std::ptr;
+use std::sync::atomic::{self, Ordering};
+use super::{Component, Modifier, Style, Text};
+use crate::inputs::{InputKey, Key};
+use crate::theme::Theme;
+
+const DEFAULT_MASK: char = '•';
+
+/// * PasswordInput - a single line input that draws a mask character in place of what's typed
+///
+/// Like `Modifier::Concealed` text, the value is there but not shown: only the mask is ever rendered, unless the
+/// user toggles it visible with the reveal key. Add it with `Ui::password_input`, which also keeps the value out
+/// of recordings and snapshots while it's on screen.
+///
+/// The input's own buffer is wiped from memory when the input is dropped or cleared, and before it's given up for a
+/// bigger one. For the same reason the input can't be cloned, and its `Debug` output leaves the value out. Only that
+/// buffer is wiped: what `value` hands out, and the text drawn while the value is revealed (the spans of `to_text`,
+/// the rendered frame and what's sent to the terminal), are ordinary strings that aren't.
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut password = PasswordInput::new().with_width(10);
+/// for c in "hunter2".chars() {
+///     password.handle_key(Key::from_char(c).unwrap());
+/// }
+/// assert_eq!(password.value(), "hunter2");
+/// assert_eq!(password.to_text(&Theme::dark()).render_plain(), "•••••••   ");
+///
+/// password.toggle_reveal();
+/// assert_eq!(password.to_text(&Theme::dark()).render_plain(), "hunter2   ");
+///
+/// // Without a mask the field is concealed, whatever was typed
+/// let hidden = PasswordInput::new().with_width(10).with_mask(None).with_focus(false);
+/// let mut typed = PasswordInput::new().with_width(10).with_mask(None).with_focus(false);
+/// typed.handle_key(Key::from_char('x').unwrap());
+/// let text = typed.to_text(&Theme::dark());
+/// assert_eq!(text.render_plain(), "          ");
+/// assert!(text.spans().iter().any(|s| s.get_style().has(Modifier::Concealed)));
+/// assert!(!hidden.to_text(&Theme::dark()).spans().iter().any(|s| s.get_style().has(Modifier::Concealed)));
+/// ```
+pub struct PasswordInput {
+    value: String,
+    /// The cursor, counted in characters
+    cursor: usize,
+    mask: Option<char>,
+    revealed: bool,
+    reveal_key: Option<Key>,
+    placeholder: String,
+    width: usize,
+    focused: bool,
+}
+
+impl Default for PasswordInput {
+
+    fn default() -> Self {
+        Self {
+            value: String::with_capacity(32),
+            cursor: 0,
+            mask: Some(DEFAULT_MASK),
+            revealed: false,
+            reveal_key: None,
+            placeholder: String::new(),
+            width: 20,
+            focused: true,
+        }
+    }
+}
+
+impl fmt::Debug for PasswordInput {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordInput")
+            .field("length", &self.len())
+            .field("revealed", &self.revealed)
+            .field("focused", &self.focused)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for PasswordInput {
+
+    fn drop(&mut self) {
+        wipe(&mut self.value);
+    }
+}
+
+/// Overwrites every byte the string has allocated, spare capacity included, and empties it
+fn wipe(value: &mut String) {
+    // SAFETY: the string is emptied first, so no invalid UTF-8 is ever left in it
+    let bytes = unsafe { value.as_mut_vec() };
+    bytes.clear();
+    for byte in bytes.spare_capacity_mut() {
+        // Volatile writes can't be optimized away, even though nothing reads the bytes again
+        unsafe { ptr::write_volatile(byte.as_mut_ptr(), 0) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// The byte range of the character at `column` in the plain rendering of `text`
+fn char_range(text: &Text, column: usize) -> Option<Range<usize>> {
+    let plain = text.render_plain();
+    let (start, c) = plain.char_indices().nth(column)?;
+    Some(start..start + c.len_utf8())
+}
+
+impl PasswordInput {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+
+    /// The character drawn for every typed one. With `None` the whole field is drawn as `Modifier::Concealed` blanks
+    /// once something is typed, which doesn't even show how much was, and the cursor stays at the start
+    pub fn with_mask(mut self, mask: Option<char>) -> Self {
+        self.mask = mask; self
+    }
+
+
+    /// A key that shows and hides the value, like `Key::new(InputKey::F2)`
+    pub fn with_reveal_key(mut self, key: Key) -> Self {
+        self.reveal_key = Some(key); self
+    }
+
+
+    /// Shown in the theme's `muted` color while nothing is typed
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string(); self
+    }
+
+
+    /// The number of columns the input takes, cursor included
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1); self
+    }
+
+
+    /// Only a focused input draws its cursor
+    pub fn with_focus(mut self, focused: bool) -> Self {
+        self.focused = focused; self
+    }
+
+
+    pub fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+
+    /// The number of characters typed
+    pub fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+
+    /// Wipes the value
+    pub fn clear(&mut self) {
+        wipe(&mut self.value);
+        self.cursor = 0;
+    }
+
+
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+
+    pub fn set_revealed(&mut self, revealed: bool) {
+        self.revealed = revealed;
+    }
+
+
+    pub fn toggle_reveal(&mut self) {
+        self.revealed = !self.revealed;
+    }
+
+
+    /// * Types, deletes or moves the cursor as the key says, and toggles the reveal key. Returns `false` for keys the input doesn't use
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        if self.reveal_key.is_some_and(|k| k.key == key.key && k.modifiers == key.modifiers) {
+            self.toggle_reveal();
+            return true;
+        }
+        match key.key {
+            InputKey::LeftArrow => self.cursor = self.cursor.saturating_sub(1),
+            InputKey::RightArrow => self.cursor = (self.cursor + 1).min(self.len()),
+            InputKey::Home => self.cursor = 0,
+            InputKey::End => self.cursor = self.len(),
+            InputKey::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.value.remove(self.byte_index(self.cursor));
+            }
+            InputKey::Delete if self.cursor < self.len() => {
+                self.value.remove(self.byte_index(self.cursor));
+            }
+            InputKey::Backspace | InputKey::Delete => {}
+            _ if key.ctrl() || key.alt() => return false,
+            _ => match key.character.or_else(|| key.key.to_char()) {
+                Some(c) => self.insert(c),
+                None => return false,
+            },
+        }
+        true
+    }
+
+
+    /// * Lays the input out like it's rendered: the mask or the value, the cursor, and padding up to the width
+    pub fn to_text(&self, theme: &Theme) -> Text {
+        self.layout(theme, self.revealed)
+    }
+
+
+    /// The input as it looks while hidden, whether it's revealed or not
+    pub(crate) fn masked_text(&self, theme: &Theme) -> Text {
+        self.layout(theme, false)
+    }
+
+
+    fn layout(&self, theme: &Theme, revealed: bool) -> Text {
+        let length = if revealed || self.mask.is_some() { self.len() } else { 0 };
+        let cursor = self.cursor.min(length);
+        let start = (cursor + 1).saturating_sub(self.width);
+        // Only the visible part is copied out of the value
+        let shown: String = match (revealed, self.mask) {
+            (true, _) => self.value.chars().skip(start).take(self.width).collect(),
+            (false, Some(mask)) => iter::repeat_n(mask, (length - start).min(self.width)).collect(),
+            (false, None) => String::new(),
+        };
+
+        let mut text = Text::new();
+        if self.is_empty() && !self.placeholder.is_empty() {
+            let placeholder = self.placeholder.chars().take(self.width).collect::<String>();
+            text.span(&placeholder).color(theme.muted);
+        } else {
+            text.span(&shown);
+        }
+        let padding = self.width.saturating_sub(text.width());
+        if padding > 0 {
+            let blank = text.span(&" ".repeat(padding));
+            if !revealed && self.mask.is_none() && !self.is_empty() {
+                blank.concealed();
+            }
+        }
+
+        if self.focused {
+            let column = cursor - start;
+            // The cursor stays visible on a concealed field
+            let cursor_style = Style::from(Modifier::ReverseVideo).without_modifier(Modifier::Concealed);
+            text = text.highlight(char_range(&text, column), cursor_style);
+        }
+        text
+    }
+
+
+    fn byte_index(&self, column: usize) -> usize {
+        self.value.char_indices().nth(column).map_or(self.value.len(), |(i, _)| i)
+    }
+
+
+    /// Grows the buffer by hand when it's full, so the old allocation can be wiped rather than freed with the value in it
+    fn insert(&mut self, c: char) {
+        if self.value.len() + c.len_utf8() > self.value.capacity() {
+            let mut grown = String::with_capacity((self.value.capacity() * 2).max(self.value.len() + c.len_utf8()));
+            grown.push_str(&self.value);
+            wipe(&mut self.value);
+            self.value = grown;
+        }
+        let index = self.byte_index(self.cursor);
+        self.value.insert(index, c);
+        self.cursor += 1;
+    }
+}
+
+impl Component for PasswordInput {
+
+    fn render(&self) -> String {
+        self.to_text(&Theme::default()).render()
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        self.to_text(theme).render()
+    }
+}
@@ -0,0 +1,85 @@
+use std::ops::Range;
+use super::style::Style;
+use super::text::Text;
+
+impl Text {
+
+    /// * The byte ranges of every match of `needle` in `render_plain`, without overlaps
+    ///
+    /// The ranges can be handed to `highlight`, or used to index the plain text.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("the penguin "), span!("waddles", italic));
+    /// assert_eq!(text.find("n"), [6..7, 10..11]);
+    /// assert!(text.find("walrus").is_empty());
+    /// ```
+    pub fn find(&self, needle: &str) -> Vec<Range<usize>> {
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        self.render_plain()
+            .match_indices(needle)
+            .map(|(start, m)| start..start + m.len())
+            .collect()
+    }
+
+
+    /// * The byte ranges of every match of a regular expression in `render_plain`
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text: Text = "error at 12:7, error at 40:2".into();
+    /// let position = tuitui::regex::Regex::new(r"\d+:\d+").unwrap();
+    /// assert_eq!(text.find_regex(&position), [9..13, 24..28]);
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn find_regex(&self, regex: &regex::Regex) -> Vec<Range<usize>> {
+        regex.find_iter(&self.render_plain()).map(|m| m.range()).collect()
+    }
+
+
+    /// * Lays `style` over the given byte ranges of `render_plain`, splitting spans where the ranges start and end
+    ///
+    /// Everything else about the spans is kept, so a match inside a link stays part of the link. Range ends that
+    /// fall inside a character are ignored.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("a wild "), span!("penguin", color Color::Blue));
+    /// let marked = text.highlight(text.find("in"), Style::new().with_bg(Color::Yellow));
+    ///
+    /// let spans = marked.spans();
+    /// assert_eq!(spans.iter().map(|s| s.render_plain()).collect::<Vec<_>>(), ["a wild ", "pengu", "in"]);
+    /// assert_eq!(spans[2].get_style().fg, Some(Color::Blue));
+    /// assert_eq!(spans[2].get_style().bg, Some(Color::Yellow));
+    /// ```
+    pub fn highlight<I: IntoIterator<Item = Range<usize>>>(&self, ranges: I, style: Style) -> Text {
+        let ranges = ranges.into_iter().filter(|r| r.start < r.end).collect::<Vec<_>>();
+        let mut text = Text::new();
+        let mut offset = 0;
+
+        for span in &self.spans {
+            let content = span.content();
+            let mut cuts = vec![0, content.len()];
+            for range in &ranges {
+                for point in [range.start, range.end] {
+                    if point > offset && point < offset + content.len() && content.is_char_boundary(point - offset) {
+                        cuts.push(point - offset);
+                    }
+                }
+            }
+            cuts.sort_unstable();
+            cuts.dedup();
+
+            for piece in cuts.windows(2) {
+                let mut part = span.clone();
+                part.contents(&content[piece[0]..piece[1]]);
+                if ranges.iter().any(|r| r.contains(&(offset + piece[0]))) {
+                    part.style(style);
+                }
+                text.span_from(part);
+            }
+            offset += content.len();
+        }
+        text
+    }
+}
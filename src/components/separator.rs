@@ -1,10 +1,13 @@
 use super::Component;
-use super::Text;
+use super::{Gradient, Style, Text};
+use crate::theme::Theme;
 
 /// * Separator - a horizontal seperator that repeats the string it was handed
 /// 
 /// ```no_run
-/// ui.separator("*+", 10)
+/// # use tuitui::prelude::*;
+/// # let mut ui = Ui::new();
+/// ui.separator("*+".into(), 10);
 /// ```
 /// 
 /// Output:
@@ -13,22 +16,82 @@ use super::Text;
 /// ```
 pub struct Separator {
     text: Text,
-    repeat: usize
+    repeat: usize,
+    width: Option<usize>,
+    gradient: Option<Gradient>
 }
 
 impl Separator {
     
     pub fn new(text: Text, repeat: usize) -> Self {
         Self {
-            text: text,
-            repeat
+            text,
+            repeat,
+            width: None,
+            gradient: None
         }
     }
+
+
+    /// * A separator repeating its pattern to exactly `width` columns, cutting the last repetition short if needed
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let separator = Separator::fill("=-".into(), 5);
+    /// assert_eq!(separator.width(), 5);
+    /// ```
+    pub fn fill(text: Text, width: usize) -> Self {
+        let pattern_width = text.width().max(1);
+        Self {
+            repeat: width.div_ceil(pattern_width),
+            text,
+            width: Some(width),
+            gradient: None
+        }
+    }
+
+
+    /// Colors the whole separator with a gradient, instead of repeating the colors of the pattern
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = Some(gradient); self
+    }
+
+
+    /// The number of columns the separator takes
+    pub fn width(&self) -> usize {
+        self.width.unwrap_or(self.text.width() * self.repeat)
+    }
 }
 
 impl Component for Separator {
     
     fn render(&self) -> String {
-        self.text.render().repeat(self.repeat)
+        self.styled_text().render()
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        self.styled_text().inherit(Style { fg: theme.border, ..Style::new() }).render()
+    }
+}
+
+impl Separator {
+
+    fn styled_text(&self) -> Text {
+        let mut text = Text::new();
+        for _ in 0..self.repeat {
+            self.text.spans.iter().for_each(|s| text.span_from(s.clone()));
+        }
+        if let Some(max_width) = self.width {
+            text.truncate(max_width);
+            let missing = max_width.saturating_sub(text.width());
+            if missing > 0 {
+                text.span(&" ".repeat(missing));
+            }
+        }
+        match &self.gradient {
+            Some(gradient) => text.gradient(gradient),
+            None => text
+        }
     }
 }
\ No newline at end of file
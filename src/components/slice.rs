@@ -0,0 +1,65 @@
+use std::ops::{Bound, RangeBounds};
+use super::text::Text;
+use super::wrap::Item;
+use crate::width;
+
+/// The items of a line lying entirely between the columns `start` and `end`
+fn slice_line(line: Vec<Item<'_>>, start: usize, end: usize) -> Vec<Item<'_>> {
+    let mut column = 0;
+    line.into_iter()
+        .filter(|(g, _)| {
+            let from = column;
+            column += width::grapheme_width(g);
+            from >= start && column <= end
+        })
+        .collect()
+}
+
+impl Text {
+
+    /// * Keeps the given range of columns of every line, with the styles of the spans it cuts through
+    ///
+    /// Grapheme clusters are never split: a wide character that only partly lies in the range is left out.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("tui", bold), span!("tui\npenguin"));
+    /// assert_eq!(text.slice(2..5).render_plain(), "itu\nngu");
+    /// assert_eq!(text.slice(3..).render_plain(), "tui\nguin");
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, columns: R) -> Text {
+        let start = match columns.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match columns.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => usize::MAX,
+        };
+        let lines = self
+            .lines()
+            .into_iter()
+            .map(|line| (0, slice_line(line, start, end)))
+            .collect();
+        self.join_lines(lines)
+    }
+
+
+    /// * Splits the text at its line breaks, keeping the styles of every span
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("one\ntwo", bold), span!("\nthree"));
+    /// let lines = text.split_lines();
+    /// assert_eq!(lines.len(), 3);
+    /// assert!(lines[1].spans()[0].get_style().has(Modifier::Bold));
+    /// assert_eq!(lines[2].render_plain(), "three");
+    /// ```
+    pub fn split_lines(&self) -> Vec<Text> {
+        self.lines()
+            .into_iter()
+            .map(|line| self.join_lines(vec![(0, line)]))
+            .collect()
+    }
+}
@@ -0,0 +1,373 @@
+use std::ops::{BitOr, Sub};
+use super::text::Color;
+
+/// * A text attribute that can be turned on or off, like bold or italic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    Blink,
+    ReverseVideo,
+    Concealed,
+    Strikethrough
+}
+
+impl Modifier {
+
+    pub const ALL: [Modifier; 8] = [
+        Modifier::Bold,
+        Modifier::Dim,
+        Modifier::Italic,
+        Modifier::Underlined,
+        Modifier::Blink,
+        Modifier::ReverseVideo,
+        Modifier::Concealed,
+        Modifier::Strikethrough,
+    ];
+
+
+    pub fn ansi(&self) -> String {
+        format!("\x1b[{}m", self.sgr_on())
+    }
+
+
+    /// The SGR parameter turning the modifier on
+    pub(crate) fn sgr_on(&self) -> u8 {
+        match self {
+            Modifier::Bold => 1,
+            Modifier::Dim => 2,
+            Modifier::Italic => 3,
+            Modifier::Underlined => 4,
+            Modifier::Blink => 5,
+            Modifier::ReverseVideo => 7,
+            Modifier::Concealed => 8,
+            Modifier::Strikethrough => 9,
+        }
+    }
+
+
+    /// The SGR parameter turning the modifier off. Bold and dim share one
+    pub(crate) fn sgr_off(&self) -> u8 {
+        match self {
+            Modifier::Bold | Modifier::Dim => 22,
+            other => other.sgr_on() + 20,
+        }
+    }
+
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// * The shape of the line drawn under `Modifier::Underlined` text
+///
+/// Only `Single` is understood everywhere. The others need a terminal with truecolor support (see
+/// `ColorDepth`), and become a single underline on every other one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UnderlineStyle {
+    #[default]
+    Single,
+    Double,
+    /// A wavy line, like the ones spell checkers draw
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+
+    /// The SGR parameter turning the underline on, `4` for a plain one and `4:n` for the others
+    pub(crate) fn sgr(&self) -> &'static str {
+        match self {
+            UnderlineStyle::Single => "4",
+            UnderlineStyle::Double => "4:2",
+            UnderlineStyle::Curly => "4:3",
+            UnderlineStyle::Dotted => "4:4",
+            UnderlineStyle::Dashed => "4:5",
+        }
+    }
+}
+
+/// * A set of `Modifier`s, stored as bit flags
+///
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut modifiers = Modifier::Bold | Modifier::Italic;
+/// modifiers.insert(Modifier::Bold);
+/// modifiers.remove(Modifier::Italic);
+/// assert_eq!(modifiers.iter().collect::<Vec<_>>(), [Modifier::Bold]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+
+    pub const fn contains(&self, modifier: Modifier) -> bool {
+        self.0 & modifier.bit() != 0
+    }
+
+
+    pub fn insert(&mut self, modifier: Modifier) {
+        self.0 |= modifier.bit();
+    }
+
+
+    pub fn remove(&mut self, modifier: Modifier) {
+        self.0 &= !modifier.bit();
+    }
+
+
+    pub fn iter(&self) -> impl Iterator<Item = Modifier> + '_ {
+        Modifier::ALL.into_iter().filter(|m| self.contains(*m))
+    }
+}
+
+impl From<Modifier> for Modifiers {
+
+    fn from(modifier: Modifier) -> Self {
+        Self(modifier.bit())
+    }
+}
+
+impl FromIterator<Modifier> for Modifiers {
+
+    fn from_iter<I: IntoIterator<Item = Modifier>>(iter: I) -> Self {
+        let mut modifiers = Self::empty();
+        iter.into_iter().for_each(|m| modifiers.insert(m));
+        modifiers
+    }
+}
+
+impl<M: Into<Modifiers>> BitOr<M> for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, other: M) -> Modifiers {
+        Modifiers(self.0 | other.into().0)
+    }
+}
+
+impl<M: Into<Modifiers>> BitOr<M> for Modifier {
+    type Output = Modifiers;
+
+    fn bitor(self, other: M) -> Modifiers {
+        Modifiers::from(self) | other
+    }
+}
+
+impl<M: Into<Modifiers>> Sub<M> for Modifiers {
+    type Output = Modifiers;
+
+    fn sub(self, other: M) -> Modifiers {
+        Modifiers(self.0 & !other.into().0)
+    }
+}
+
+/// * The colors and modifiers of a piece of text
+///
+/// Every part is optional, so a style can be laid over another one with `patch`: colors it leaves
+/// unset and modifiers it neither adds nor removes are taken from the style underneath. That's how
+/// spans inherit from the `Text` or component around them.
+/// ```
+/// # use tuitui::prelude::*;
+/// let parent = Style::new().with_fg(Color::Blue).with_modifier(Modifier::Bold);
+/// let child = Style::new().with_fg(Color::Red).without_modifier(Modifier::Bold);
+/// let patched = parent.patch(child);
+///
+/// assert_eq!(patched.fg, Some(Color::Red));
+/// assert!(!patched.has(Modifier::Bold));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub underline_color: Option<Color>,
+    /// The shape of the underline when `Modifier::Underlined` is on, a single line when `None`
+    pub underline_style: Option<UnderlineStyle>,
+    /// Modifiers turned on
+    pub add_modifier: Modifiers,
+    /// Modifiers turned off, even when the style underneath turns them on
+    pub sub_modifier: Modifiers,
+}
+
+/// What changes between two resolved styles
+#[derive(Debug, Default)]
+pub(crate) struct StyleDiff {
+    pub off: Modifiers,
+    /// Never holds `Underlined`, which is sent through `underline` with its shape
+    pub on: Modifiers,
+    pub underline: Option<UnderlineStyle>,
+    /// `Some(None)` goes back to the terminal's own color
+    pub fg: Option<Option<Color>>,
+    pub bg: Option<Option<Color>>,
+    pub underline_color: Option<Option<Color>>,
+}
+
+impl Style {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+
+    pub fn with_fg(mut self, color: Color) -> Self {
+        self.fg = Some(color); self
+    }
+
+
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.bg = Some(color); self
+    }
+
+
+    pub fn with_underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color); self
+    }
+
+
+    /// Underlines with the given shape
+    pub fn with_underline(mut self, underline: UnderlineStyle) -> Self {
+        self.underline_style = Some(underline);
+        self.with_modifier(Modifier::Underlined)
+    }
+
+
+    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.add_modifier.insert(modifier);
+        self.sub_modifier.remove(modifier);
+        self
+    }
+
+
+    pub fn without_modifier(mut self, modifier: Modifier) -> Self {
+        self.sub_modifier.insert(modifier);
+        self.add_modifier.remove(modifier);
+        self
+    }
+
+
+    /// * Lays `other` over this style: its colors replace these ones, and its modifiers are added or removed
+    pub fn patch(self, other: Style) -> Style {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            underline_color: other.underline_color.or(self.underline_color),
+            underline_style: other.underline_style.or(self.underline_style),
+            add_modifier: (self.add_modifier - other.sub_modifier) | other.add_modifier,
+            sub_modifier: (self.sub_modifier - other.add_modifier) | other.sub_modifier,
+        }
+    }
+
+
+    /// The modifiers the style turns on
+    pub fn modifiers(&self) -> Modifiers {
+        self.add_modifier - self.sub_modifier
+    }
+
+
+    pub fn has(&self, modifier: Modifier) -> bool {
+        self.modifiers().contains(modifier)
+    }
+
+
+    /// The shape of the underline actually drawn, if any
+    pub fn underline(&self) -> Option<UnderlineStyle> {
+        self.has(Modifier::Underlined).then(|| self.underline_style.unwrap_or_default())
+    }
+
+
+    /// Whether the style draws anything differently from plain text
+    pub fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.underline_color.is_none() && self.modifiers().is_empty()
+    }
+
+
+    /// What has to be sent to go from `previous` to this style
+    pub(crate) fn diff(&self, previous: &Style) -> StyleDiff {
+        let (from, to) = (previous.modifiers(), self.modifiers());
+        let off = from - to;
+        let mut on = to - from - Modifier::Underlined;
+        let underline = self.underline().filter(|u| previous.underline() != Some(*u));
+
+        // Bold and dim are turned off together, so the one that stays has to come back
+        if off.contains(Modifier::Bold) || off.contains(Modifier::Dim) {
+            for modifier in [Modifier::Bold, Modifier::Dim] {
+                if to.contains(modifier) {
+                    on.insert(modifier);
+                }
+            }
+        }
+        let changed = |a: Option<Color>, b: Option<Color>| (a != b).then_some(b);
+        StyleDiff {
+            off,
+            on,
+            underline,
+            fg: changed(previous.fg, self.fg),
+            bg: changed(previous.bg, self.bg),
+            underline_color: changed(previous.underline_color, self.underline_color),
+        }
+    }
+
+
+    /// * The shortest SGR sequence switching from `previous` to this style, empty if nothing changes
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let bold = Style::new().with_modifier(Modifier::Bold);
+    /// let bold_red = bold.with_fg(Color::Red);
+    /// assert_eq!(bold_red.sgr(&bold), "\x1b[31m");
+    /// assert_eq!(Style::new().sgr(&bold_red), "\x1b[0m");
+    /// ```
+    pub fn sgr(&self, previous: &Style) -> String {
+        if self.is_plain() {
+            return if previous.is_plain() { String::new() } else { "\x1b[0m".to_string() };
+        }
+
+        let diff = self.diff(previous);
+        let mut params = Vec::new();
+        for modifier in diff.off.iter() {
+            let off = modifier.sgr_off().to_string();
+            if !params.contains(&off) {
+                params.push(off);
+            }
+        }
+        params.extend(diff.on.iter().map(|m| m.sgr_on().to_string()));
+        if let Some(underline) = diff.underline {
+            params.push(underline.sgr().to_string());
+        }
+        if let Some(fg) = diff.fg {
+            params.push(fg.map_or("39".to_string(), |c| c.fg_params()));
+        }
+        if let Some(bg) = diff.bg {
+            params.push(bg.map_or("49".to_string(), |c| c.bg_params()));
+        }
+        if let Some(underline) = diff.underline_color {
+            params.push(underline.map_or("59".to_string(), |c| c.underline_params()));
+        }
+
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("\x1b[{}m", params.join(";"))
+        }
+    }
+}
+
+impl From<Modifier> for Style {
+
+    fn from(modifier: Modifier) -> Self {
+        Style::new().with_modifier(modifier)
+    }
+}
@@ -0,0 +1,471 @@
+//! Small hand-written lexers for the languages `Code` knows. They only tell the kinds of tokens apart for
+//! coloring, so they're forgiving: anything they don't recognise becomes plain text.
+
+use super::style::{Modifier, Style};
+use crate::theme::Theme;
+
+/// * The languages `Code` can highlight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    Rust,
+    Toml,
+    Json,
+    Yaml,
+    /// POSIX shells and bash
+    Shell,
+    /// No highlighting at all
+    #[default]
+    Plain,
+}
+
+impl Language {
+
+    /// Picks a language by name or common alias, like the info string of a markdown code block. Unknown names get `Plain`
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "rust" | "rs" => Self::Rust,
+            "toml" => Self::Toml,
+            "json" | "jsonc" => Self::Json,
+            "yaml" | "yml" => Self::Yaml,
+            "shell" | "sh" | "bash" | "zsh" | "console" => Self::Shell,
+            _ => Self::Plain,
+        }
+    }
+
+
+    /// Picks a language from a file extension, with or without its dot
+    pub fn from_extension(extension: &str) -> Self {
+        Self::from_name(extension.trim_start_matches('.'))
+    }
+}
+
+/// What a piece of source is, as far as coloring goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Plain,
+    Keyword,
+    String,
+    Number,
+    /// `true`, `false`, `null` and the like
+    Constant,
+    Comment,
+    Type,
+    /// Function calls, macros and shell commands
+    Function,
+    /// Keys of config files
+    Key,
+    /// Shell variables, YAML anchors and Rust lifetimes
+    Variable,
+    /// Rust attributes
+    Attribute,
+}
+
+impl TokenKind {
+
+    pub(crate) fn style(&self, theme: &Theme) -> Style {
+        match self {
+            TokenKind::Plain => Style::new(),
+            TokenKind::Keyword => Style::new().with_fg(theme.primary).with_modifier(Modifier::Bold),
+            TokenKind::String => Style::new().with_fg(theme.success),
+            TokenKind::Number | TokenKind::Constant => Style::new().with_fg(theme.warning),
+            TokenKind::Comment => Style::new().with_fg(theme.muted).with_modifier(Modifier::Italic),
+            TokenKind::Type => Style::new().with_fg(theme.secondary),
+            TokenKind::Function => Style::new().with_fg(theme.accent),
+            TokenKind::Key => Style::new().with_fg(theme.primary),
+            TokenKind::Variable => Style::new().with_fg(theme.focus),
+            TokenKind::Attribute => Style::new().with_fg(theme.muted),
+        }
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "type", "unsafe", "use", "where", "while",
+];
+
+const SHELL_KEYWORDS: &[&str] = &[
+    "if", "then", "elif", "else", "fi", "for", "while", "until", "do", "done", "case", "esac", "in", "function",
+    "select", "return", "local", "export", "readonly",
+];
+
+/// Shell keywords after which a new command starts
+const SHELL_COMMAND_STARTS: &[&str] = &["if", "then", "elif", "else", "do", "while", "until", "!"];
+
+struct Lexer<'a> {
+    source: &'a str,
+    position: usize,
+    tokens: Vec<(TokenKind, &'a str)>,
+}
+
+impl<'a> Lexer<'a> {
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+
+    /// Takes the next `len` bytes as one token, merged with the one before if it's of the same kind
+    fn push(&mut self, kind: TokenKind, len: usize) {
+        let len = len.max(self.peek().map_or(0, char::len_utf8));
+        let end = (self.position + len).min(self.source.len());
+        match self.tokens.last_mut() {
+            Some((last, content)) if *last == kind => {
+                *content = &self.source[self.position - content.len()..end];
+            }
+            _ => self.tokens.push((kind, &self.source[self.position..end])),
+        }
+        self.position = end;
+    }
+
+
+    /// The length of the longest prefix of what's left whose characters all match
+    fn prefix_len(&self, matches: impl Fn(char) -> bool) -> usize {
+        self.rest().find(|c| !matches(c)).unwrap_or(self.rest().len())
+    }
+
+
+    fn to_end_of_line(&self) -> usize {
+        self.rest().find('\n').unwrap_or(self.rest().len())
+    }
+
+
+    /// Whether only whitespace comes between the start of the line and the current position
+    fn at_line_start(&self) -> bool {
+        let line = &self.source[..self.position];
+        line[line.rfind('\n').map_or(0, |i| i + 1)..].trim().is_empty()
+    }
+
+
+    /// The first character after the current token and the spaces following it
+    fn next_after(&self, len: usize) -> Option<char> {
+        self.rest()[len..].trim_start_matches([' ', '\t']).chars().next()
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The length of a string starting with `quote`, closing quote included. Backslashes escape when `escapes`
+/// is set, and single line strings stop at the end of the line if they aren't closed
+fn string_len(rest: &str, quote: char, escapes: bool, multiline: bool) -> usize {
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if escapes => escaped = true,
+            '\n' if !multiline => return i,
+            c if c == quote => return i + c.len_utf8(),
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+/// The length of a number: digits, letters for suffixes and bases, underscores and dots followed by a digit
+fn number_len(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let b = bytes[len];
+        let decimal_point = b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        let exponent = (b == b'+' || b == b'-') && len > 0 && matches!(bytes[len - 1], b'e' | b'E') && !rest.starts_with("0x");
+        if !(b.is_ascii_alphanumeric() || b == b'_' || decimal_point || exponent) {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+fn rust(lexer: &mut Lexer) {
+    while let Some(c) = lexer.peek() {
+        let rest = lexer.rest();
+        if rest.starts_with("//") {
+            lexer.push(TokenKind::Comment, lexer.to_end_of_line());
+        } else if rest.starts_with("/*") {
+            lexer.push(TokenKind::Comment, rest.find("*/").map_or(rest.len(), |i| i + 2));
+        } else if let Some(len) = raw_string_len(rest) {
+            lexer.push(TokenKind::String, len);
+        } else if c == '"' {
+            lexer.push(TokenKind::String, string_len(rest, '"', true, true));
+        } else if rest.starts_with("b\"") || rest.starts_with("b'") {
+            lexer.push(TokenKind::String, 1 + string_len(&rest[1..], rest.as_bytes()[1] as char, true, true));
+        } else if c == '\'' {
+            match char_literal_len(rest) {
+                Some(len) => lexer.push(TokenKind::String, len),
+                None => lexer.push(TokenKind::Variable, 1 + rest[1..].find(|c| !is_ident(c)).unwrap_or(rest.len() - 1)),
+            }
+        } else if c.is_ascii_digit() {
+            lexer.push(TokenKind::Number, number_len(rest));
+        } else if rest.starts_with("#[") || rest.starts_with("#![") {
+            lexer.push(TokenKind::Attribute, bracketed_len(rest));
+        } else if is_ident_start(c) {
+            let len = lexer.prefix_len(is_ident);
+            let word = &rest[..len];
+            let kind = if RUST_KEYWORDS.contains(&word) {
+                TokenKind::Keyword
+            } else if word == "true" || word == "false" {
+                TokenKind::Constant
+            } else if rest[len..].starts_with('!') && !rest[len..].starts_with("!=") {
+                lexer.push(TokenKind::Function, len + 1);
+                continue;
+            } else if lexer.next_after(len) == Some('(') {
+                TokenKind::Function
+            } else if word.starts_with(char::is_uppercase) {
+                TokenKind::Type
+            } else {
+                TokenKind::Plain
+            };
+            lexer.push(kind, len);
+        } else {
+            lexer.push(TokenKind::Plain, c.len_utf8());
+        }
+    }
+}
+
+/// The length of `r"…"`, `r#"…"#` or `br"…"`, if that's what `rest` starts with
+fn raw_string_len(rest: &str) -> Option<usize> {
+    let after_prefix = rest.strip_prefix("br").or_else(|| rest.strip_prefix('r'))?;
+    let hashes = after_prefix.len() - after_prefix.trim_start_matches('#').len();
+    let body = after_prefix[hashes..].strip_prefix('"')?;
+    let closing = format!("\"{}", "#".repeat(hashes));
+    let start = rest.len() - body.len();
+    Some(body.find(&closing).map_or(rest.len(), |i| start + i + closing.len()))
+}
+
+/// The length of a character literal like `'a'` or `'\n'`, or `None` for a lifetime
+fn char_literal_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        return rest[2..].find('\'').map(|i| i + 3);
+    }
+    let (i, closing) = chars.next()?;
+    (closing == '\'').then_some(i + 1)
+}
+
+/// The length of `#[…]`, counting nested brackets
+fn bracketed_len(rest: &str) -> usize {
+    let mut depth = 0;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 1 => return i + 1,
+            ']' => depth -= 1,
+            '\n' => return i,
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+fn toml(lexer: &mut Lexer) {
+    while let Some(c) = lexer.peek() {
+        let rest = lexer.rest();
+        if c == '#' {
+            lexer.push(TokenKind::Comment, lexer.to_end_of_line());
+        } else if c == '[' && lexer.at_line_start() {
+            let len = rest.find(['\n', '#']).unwrap_or(rest.len());
+            lexer.push(TokenKind::Type, rest[..len].trim_end().len());
+        } else if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+            let quotes = &rest[..3];
+            lexer.push(TokenKind::String, rest[3..].find(quotes).map_or(rest.len(), |i| i + 6));
+        } else if c == '"' || c == '\'' {
+            let len = string_len(rest, c, c == '"', false);
+            let kind = if lexer.next_after(len) == Some('=') { TokenKind::Key } else { TokenKind::String };
+            lexer.push(kind, len);
+        } else if c.is_ascii_digit() || ((c == '+' || c == '-') && rest[1..].starts_with(|c: char| c.is_ascii_digit())) {
+            let len = lexer.prefix_len(|c| c.is_ascii_alphanumeric() || "_.:-+".contains(c));
+            lexer.push(TokenKind::Number, len);
+        } else if is_ident(c) || c == '-' {
+            let len = lexer.prefix_len(|c| is_ident(c) || c == '-');
+            let kind = match &rest[..len] {
+                _ if matches!(lexer.next_after(len), Some('=' | '.')) => TokenKind::Key,
+                "true" | "false" => TokenKind::Constant,
+                "inf" | "nan" => TokenKind::Number,
+                _ => TokenKind::Plain,
+            };
+            lexer.push(kind, len);
+        } else {
+            lexer.push(TokenKind::Plain, c.len_utf8());
+        }
+    }
+}
+
+fn json(lexer: &mut Lexer) {
+    while let Some(c) = lexer.peek() {
+        let rest = lexer.rest();
+        if rest.starts_with("//") {
+            lexer.push(TokenKind::Comment, lexer.to_end_of_line());
+        } else if c == '"' {
+            let len = string_len(rest, '"', true, false);
+            let kind = if lexer.next_after(len) == Some(':') { TokenKind::Key } else { TokenKind::String };
+            lexer.push(kind, len);
+        } else if c.is_ascii_digit() || (c == '-' && rest[1..].starts_with(|c: char| c.is_ascii_digit())) {
+            lexer.push(TokenKind::Number, 1 + number_len(&rest[1..]));
+        } else if is_ident_start(c) {
+            let len = lexer.prefix_len(is_ident);
+            let kind = match &rest[..len] {
+                "true" | "false" | "null" => TokenKind::Constant,
+                _ => TokenKind::Plain,
+            };
+            lexer.push(kind, len);
+        } else {
+            lexer.push(TokenKind::Plain, c.len_utf8());
+        }
+    }
+}
+
+/// The kind of an unquoted YAML value
+fn yaml_scalar(scalar: &str) -> TokenKind {
+    match scalar {
+        "true" | "false" | "True" | "False" | "TRUE" | "FALSE" | "yes" | "no" | "on" | "off" | "null" | "Null"
+        | "NULL" | "~" => TokenKind::Constant,
+        _ if scalar.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.')
+            && scalar.trim_start_matches(['-', '+']).parse::<f64>().is_ok() => TokenKind::Number,
+        _ => TokenKind::String,
+    }
+}
+
+fn yaml(lexer: &mut Lexer) {
+    let mut flow_depth = 0usize;
+    while let Some(c) = lexer.peek() {
+        let rest = lexer.rest();
+        let after_space = lexer.source[..lexer.position].ends_with([' ', '\t', '\n']) || lexer.position == 0;
+
+        if c == '#' && after_space {
+            lexer.push(TokenKind::Comment, lexer.to_end_of_line());
+        } else if (rest.starts_with("---") || rest.starts_with("...")) && lexer.at_line_start() {
+            lexer.push(TokenKind::Keyword, 3);
+        } else if c == '"' || c == '\'' {
+            let len = string_len(rest, c, c == '"', true);
+            let kind = if rest[len..].starts_with(':') { TokenKind::Key } else { TokenKind::String };
+            lexer.push(kind, len);
+        } else if c == '&' || c == '*' {
+            lexer.push(TokenKind::Variable, lexer.prefix_len(|c| !c.is_whitespace() && !",[]{}".contains(c)));
+        } else if c == '!' {
+            lexer.push(TokenKind::Type, lexer.prefix_len(|c| !c.is_whitespace()));
+        } else if c == '-' && rest[1..].starts_with(|c: char| !c.is_whitespace()) {
+            // Only `- ` starts a list item, `-1` and `-foo` are values
+            yaml_value(lexer, flow_depth);
+        } else if c.is_whitespace() || "-?:,[]{}|>%@`".contains(c) {
+            match c {
+                '[' | '{' => flow_depth += 1,
+                ']' | '}' => flow_depth = flow_depth.saturating_sub(1),
+                _ => {}
+            }
+            lexer.push(TokenKind::Plain, c.len_utf8());
+        } else {
+            yaml_value(lexer, flow_depth);
+        }
+    }
+}
+
+/// Lexes an unquoted key or value, which ends at the end of the line, a comment or a `: `
+fn yaml_value(lexer: &mut Lexer, flow_depth: usize) {
+    let rest = lexer.rest();
+    let mut end = rest.len();
+    let mut is_key = false;
+    for (i, c) in rest.char_indices() {
+        let next = rest[i + c.len_utf8()..].chars().next();
+        let ends_key = c == ':' && next.is_none_or(|n| n.is_whitespace() || (flow_depth > 0 && ",]}".contains(n)));
+        if c == '\n' || (c == '#' && rest[..i].ends_with([' ', '\t'])) || ends_key || (flow_depth > 0 && ",]}".contains(c)) {
+            end = i;
+            is_key = ends_key;
+            break;
+        }
+    }
+    let scalar = rest[..end].trim_end();
+    let kind = if is_key { TokenKind::Key } else { yaml_scalar(scalar) };
+    lexer.push(kind, scalar.len());
+}
+
+fn shell(lexer: &mut Lexer) {
+    let mut command_position = true;
+    while let Some(c) = lexer.peek() {
+        let rest = lexer.rest();
+        let word_start = lexer.position == 0 || lexer.source[..lexer.position].ends_with(|c: char| c.is_whitespace() || ";|&(".contains(c));
+
+        if c == '#' && word_start {
+            lexer.push(TokenKind::Comment, lexer.to_end_of_line());
+        } else if c == '\'' {
+            lexer.push(TokenKind::String, string_len(rest, '\'', false, true));
+            command_position = false;
+        } else if c == '"' {
+            lexer.push(TokenKind::String, string_len(rest, '"', true, true));
+            command_position = false;
+        } else if c == '$' {
+            let len = if rest.starts_with("${") {
+                rest.find('}').map_or(rest.len(), |i| i + 1)
+            } else if rest[1..].starts_with(|c: char| "@*#?$!-0123456789".contains(c)) {
+                2
+            } else {
+                1 + rest[1..].find(|c| !is_ident(c)).unwrap_or(rest.len() - 1)
+            };
+            lexer.push(if len > 1 { TokenKind::Variable } else { TokenKind::Plain }, len);
+        } else if c == '\n' || ";|&(){}`".contains(c) {
+            command_position = true;
+            lexer.push(TokenKind::Plain, 1);
+        } else if c.is_whitespace() || "<>".contains(c) {
+            lexer.push(TokenKind::Plain, c.len_utf8());
+        } else {
+            let len = lexer.prefix_len(|c| !c.is_whitespace() && !";|&()<>'\"$`{}".contains(c));
+            let word = &rest[..len];
+            if let Some(name) = word.split_once('=').map(|(n, _)| n).filter(|n| !n.is_empty() && n.chars().all(is_ident)) {
+                // `NAME=value` in front of a command or on its own
+                lexer.push(TokenKind::Variable, name.len());
+                lexer.push(TokenKind::Plain, 1);
+                // Lexed here, so a quoted value doesn't end the command position like a quoted argument would
+                let value = match lexer.peek() {
+                    Some(quote @ ('\'' | '"')) => string_len(lexer.rest(), quote, quote == '"', true),
+                    _ => lexer.prefix_len(|c| !c.is_whitespace() && !";|&()<>'\"$`".contains(c)),
+                };
+                if value > 0 {
+                    lexer.push(TokenKind::String, value);
+                }
+                continue;
+            }
+            let kind = if SHELL_KEYWORDS.contains(&word) && (command_position || word == "in") {
+                TokenKind::Keyword
+            } else if command_position {
+                TokenKind::Function
+            } else if word.starts_with('-') {
+                TokenKind::Constant
+            } else if word.chars().all(|c| c.is_ascii_digit()) {
+                TokenKind::Number
+            } else {
+                TokenKind::Plain
+            };
+            command_position = SHELL_COMMAND_STARTS.contains(&word);
+            lexer.push(kind, len);
+        }
+    }
+}
+
+/// Splits the whole source into tokens, which together hold every byte of it
+pub(crate) fn tokenize(source: &str, language: Language) -> Vec<(TokenKind, &str)> {
+    let mut lexer = Lexer { source, position: 0, tokens: Vec::new() };
+    match language {
+        Language::Rust => rust(&mut lexer),
+        Language::Toml => toml(&mut lexer),
+        Language::Json => json(&mut lexer),
+        Language::Yaml => yaml(&mut lexer),
+        Language::Shell => shell(&mut lexer),
+        Language::Plain => {
+            let len = source.len();
+            lexer.push(TokenKind::Plain, len);
+        }
+    }
+    lexer.tokens
+}
@@ -1,6 +1,9 @@
+use std::ops::{Add, AddAssign};
 use super::Component;
+use super::style::{Modifier, Style, UnderlineStyle};
+use crate::{ansi, width};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     Black,
     Red, 
@@ -19,88 +22,127 @@ pub enum Color {
     Pink,
     LightCyan,
     BrightWhite,
-    RGB(u8, u8, u8)
+    RGB(u8, u8, u8),
+    /// An entry of the 256 color palette: the 16 colors above, a 6x6x6 color cube and a grayscale ramp
+    Indexed(u8)
 }
 
 impl Color {
     
-    pub fn ansi_fg(&self) -> String {
+    /// The RGB value of the color, using the xterm palette for the named colors
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
         match self {
-            Color::Black => "\x1b[30m".to_string(),
-            Color::Red => "\x1b[31m".to_string(),
-            Color::Green => "\x1b[32m".to_string(),
-            Color::Yellow => "\x1b[33m".to_string(),
-            Color::Blue => "\x1b[34m".to_string(),
-            Color::Magenta => "\x1b[35m".to_string(),
-            Color::Cyan => "\x1b[36m".to_string(),
-            Color::White => "\x1b[37m".to_string(),
-
-            Color::Gray => "\x1b[90m".to_string(),
-            Color::BrightRed => "\x1b[91m".to_string(),
-            Color::Lime => "\x1b[92m".to_string(),
-            Color::Banana => "\x1b[93m".to_string(),
-            Color::LightBlue => "\x1b[94m".to_string(),
-            Color::Pink => "\x1b[95m".to_string(),
-            Color::LightCyan => "\x1b[96m".to_string(),
-            Color::BrightWhite => "\x1b[97m".to_string(),
-            Color::RGB(r, g, b) => format!("\x1b[38;2;{};{};{}m", r, g, b),
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 0, 0),
+            Color::Green => (0, 205, 0),
+            Color::Yellow => (205, 205, 0),
+            Color::Blue => (0, 0, 238),
+            Color::Magenta => (205, 0, 205),
+            Color::Cyan => (0, 205, 205),
+            Color::White => (229, 229, 229),
+
+            Color::Gray => (127, 127, 127),
+            Color::BrightRed => (255, 0, 0),
+            Color::Lime => (0, 255, 0),
+            Color::Banana => (255, 255, 0),
+            Color::LightBlue => (92, 92, 255),
+            Color::Pink => (255, 0, 255),
+            Color::LightCyan => (0, 255, 255),
+            Color::BrightWhite => (255, 255, 255),
+            Color::RGB(r, g, b) => (*r, *g, *b),
+            Color::Indexed(index) => match index {
+                0..=15 => ansi::named_color(*index as u16).to_rgb(),
+                16..=231 => {
+                    let i = index - 16;
+                    let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                    (level(i / 36), level((i / 6) % 6), level(i % 6))
+                }
+                _ => {
+                    let gray = 8 + (index - 232) * 10;
+                    (gray, gray, gray)
+                }
+            },
         }
     }
     
     
+    /// The color as a CSS hex string like `#7a62a8`
+    pub fn to_hex(&self) -> String {
+        let (r, g, b) = self.to_rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+    
+    
+    pub fn ansi_fg(&self) -> String {
+        format!("\x1b[{}m", self.fg_params())
+    }
+    
+    
     pub fn ansi_bg(&self) -> String {
+        format!("\x1b[{}m", self.bg_params())
+    }
+
+
+    /// The position of a named color in the 16 color palette
+    fn palette_index(&self) -> Option<u8> {
+        Some(match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+
+            Color::Gray => 8,
+            Color::BrightRed => 9,
+            Color::Lime => 10,
+            Color::Banana => 11,
+            Color::LightBlue => 12,
+            Color::Pink => 13,
+            Color::LightCyan => 14,
+            Color::BrightWhite => 15,
+            Color::RGB(..) | Color::Indexed(_) => return None,
+        })
+    }
+
+
+    /// The SGR parameters selecting the color, with `base` being 30 for the foreground and 40 for the background
+    fn params(&self, base: u8) -> String {
         match self {
-            Color::Black => "\x1b[40m".to_string(),
-            Color::Red => "\x1b[41m".to_string(),
-            Color::Green => "\x1b[42m".to_string(),
-            Color::Yellow => "\x1b[43m".to_string(),
-            Color::Blue => "\x1b[44m".to_string(),
-            Color::Magenta => "\x1b[45m".to_string(),
-            Color::Cyan => "\x1b[46m".to_string(),
-            Color::White => "\x1b[47m".to_string(),
-
-            Color::Gray => "\x1b[100m".to_string(),
-            Color::BrightRed => "\x1b[101m".to_string(),
-            Color::Lime => "\x1b[102m".to_string(),
-            Color::Banana => "\x1b[103m".to_string(),
-            Color::LightBlue => "\x1b[104m".to_string(),
-            Color::Pink => "\x1b[105m".to_string(),
-            Color::LightCyan => "\x1b[106m".to_string(),
-            Color::BrightWhite => "\x1b[107m".to_string(),
-            Color::RGB(r, g, b) => format!("\x1b[48;2;{};{};{}m", r, g, b),
+            Color::RGB(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+            Color::Indexed(index) => format!("{};5;{}", base + 8, index),
+            named => match named.palette_index().unwrap_or(0) {
+                index @ 0..=7 => (base + index).to_string(),
+                index => (base + 60 + index - 8).to_string(),
+            },
         }
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-pub enum Style {
-    Bold,
-    Dim,
-    Italic,
-    Underlined,
-    Blink,
-    ReverseVideo,
-    Concealed,
-    Strikethrough
-}
 
-impl Style {
-    
-    pub fn ansi(&self) -> String {
+    /// The SGR parameters selecting the color as the foreground, like `31` or `38;2;r;g;b`
+    pub(crate) fn fg_params(&self) -> String {
+        self.params(30)
+    }
+
+
+    pub(crate) fn bg_params(&self) -> String {
+        self.params(40)
+    }
+
+
+    /// Underline colors have no short codes, so named colors use their palette index
+    pub(crate) fn underline_params(&self) -> String {
         match self {
-            Style::Bold => "\x1b[1m",
-            Style::Dim => "\x1b[2m",
-            Style::Italic => "\x1b[3m",
-            Style::Underlined => "\x1b[4m",
-            Style::Blink => "\x1b[5m",
-            Style::ReverseVideo => "\x1b[7m",
-            Style::Concealed => "\x1b[8m",
-            Style::Strikethrough => "\x1b[9m"
-        }.to_string()
+            Color::RGB(r, g, b) => format!("58;2;{};{};{}", r, g, b),
+            Color::Indexed(index) => format!("58;5;{}", index),
+            named => format!("58;5;{}", named.palette_index().unwrap_or(0)),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Text {
     pub(crate) spans: Vec<TextSpan>
 }
@@ -114,6 +156,53 @@ impl Text {
     }
 
     
+    /// * Parses text containing SGR escape sequences, like the colored output of other tools
+    ///
+    /// 16 color, 256 color and truecolor codes, attributes and resets become spans with the matching
+    /// `Color`s and `Modifier`s. Every other escape and control sequence is dropped, tabs are expanded
+    /// to the next multiple of 8 columns and `\r\n` becomes `\n`, so the text can't break the layout around it.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = Text::from_ansi("\x1b[1;31merror\x1b[0m: not found");
+    /// assert_eq!(text.render_plain(), "error: not found");
+    /// ```
+    pub fn from_ansi(input: &str) -> Self {
+        let mut text = Text::new();
+        let mut column = 0;
+
+        for (state, content) in ansi::segments(input) {
+            let mut cleaned = String::new();
+            for grapheme in width::graphemes(&content) {
+                match grapheme {
+                    "\n" | "\r\n" => {
+                        cleaned.push('\n');
+                        column = 0;
+                    }
+                    "\t" => {
+                        let spaces = 8 - column % 8;
+                        cleaned.push_str(&" ".repeat(spaces));
+                        column += spaces;
+                    }
+                    g if g.starts_with(char::is_control) => {}
+                    g => {
+                        cleaned.push_str(g);
+                        column += width::grapheme_width(g);
+                    }
+                }
+            }
+            if cleaned.is_empty() {
+                continue;
+            }
+
+            let span = text.span(&cleaned);
+            span.style = state.style;
+            span.link = state.link;
+        }
+
+        text
+    }
+
+    
     pub fn span(&mut self, content: &str) -> &mut TextSpan {
         self.spans.push(TextSpan::new(content));
         self.spans.last_mut().unwrap()
@@ -125,6 +214,61 @@ impl Text {
     }
 
     
+    /// * A copy whose spans inherit `parent`: it fills in whatever they leave unset
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("plain "), span!("red", color Color::Red));
+    /// let blue = text.inherit(Style::new().with_fg(Color::Blue));
+    /// assert_eq!(blue.spans()[0].get_style().fg, Some(Color::Blue));
+    /// assert_eq!(blue.spans()[1].get_style().fg, Some(Color::Red));
+    /// ```
+    pub fn inherit(&self, parent: Style) -> Text {
+        let mut text = self.clone();
+        for span in &mut text.spans {
+            span.style = parent.patch(span.style);
+        }
+        text
+    }
+
+
+    /// * A copy with the target of every link written after it, like `docs (https://docs.rs)`
+    ///
+    /// That's how terminals without OSC 8 hyperlinks show them. The target is a plain span of its own, so it's wrapped and aligned with the rest of the text.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("docs", link "https://docs.rs", color Color::Blue), span!(" and more"));
+    /// let inlined = text.inline_links();
+    /// assert_eq!(inlined.render_plain(), "docs (https://docs.rs) and more");
+    /// assert_eq!(inlined.spans()[1].get_link(), None);
+    /// assert!(inlined.spans()[1].get_style().is_plain());
+    /// ```
+    pub fn inline_links(&self) -> Text {
+        let mut text = Text::new();
+        for (i, span) in self.spans.iter().enumerate() {
+            text.span_from(span.clone());
+            let next = self.spans.get(i + 1).and_then(|s| s.link.as_deref());
+            if let Some(url) = span.link.as_deref().filter(|url| next != Some(*url)) {
+                text.span(&format!(" ({})", url));
+            }
+        }
+        text
+    }
+
+
+    /// Lays `style` over every span, see `Style::patch`
+    pub fn patch_style(&mut self, style: Style) {
+        self.spans.iter_mut().for_each(|s| {
+            s.style(style);
+        })
+    }
+
+
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    
     pub fn bold_all(&mut self) {
         self.spans.iter_mut().for_each(|s| {
             s.bold();
@@ -190,15 +334,65 @@ impl Text {
 
         output
     }
+
+
+    /// * The number of terminal columns taken by the widest line
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text: Text = "猫 cat\nkitten".into();
+    /// assert_eq!(text.width(), 6);
+    /// ```
+    pub fn width(&self) -> usize {
+        width::max_line_width(&self.render_plain())
+    }
+
+
+    /// * Cuts every line down to at most `max_width` columns, keeping the styles of what's left
+    ///
+    /// Grapheme clusters are never split, so a line may end up a column short of `max_width`.
+    pub fn truncate(&mut self, max_width: usize) {
+        let mut column = 0;
+
+        for span in &mut self.spans {
+            let mut kept = String::new();
+            for (i, line) in span.content.split('\n').enumerate() {
+                if i > 0 {
+                    kept.push('\n');
+                    column = 0;
+                }
+                let fitting = width::truncate(line, max_width.saturating_sub(column));
+                kept.push_str(fitting);
+                column += if fitting.len() == line.len() { width::str_width(fitting) } else { max_width };
+            }
+            span.content = kept;
+        }
+        self.spans.retain(|s| !s.content.is_empty());
+    }
 }
 
 impl Component for Text {
     
+    /// Only the parts of the style that change between spans are sent, and it's reset once at the end
     fn render(&self) -> String {
         let mut output = String::new();
+        let mut current = Style::new();
+        let mut link: Option<&str> = None;
 
+        // A span switches to its own style before its link, so link targets written by `Text::inline_links`, which
+        // are spans of their own, are drawn plain and outside the link
         for span in &self.spans {
-            output.push_str(&span.render())
+            output.push_str(&span.style.sgr(&current));
+            if span.link.as_deref() != link {
+                output.push_str(&ansi::hyperlink(span.link.as_deref().unwrap_or("")));
+                link = span.link.as_deref();
+            }
+            output.push_str(&span.content);
+            current = span.style;
+        }
+        output.push_str(&Style::new().sgr(&current));
+        if link.is_some() {
+            output.push_str(&ansi::hyperlink(""));
         }
 
         output
@@ -208,9 +402,8 @@ impl Component for Text {
 #[derive(Debug, Clone)]
 pub struct TextSpan {
     content: String,
-    color: Option<Color>,
-    bg_color: Option<Color>,
-    styles: Vec<Style>
+    style: Style,
+    link: Option<String>
 }
 
 impl TextSpan {
@@ -218,9 +411,8 @@ impl TextSpan {
     pub fn new(content: &str) -> Self {
         Self {
             content: content.to_string(),
-            color: None,
-            bg_color: None,
-            styles: Vec::new(),
+            style: Style::new(),
+            link: None,
         }
     }
 
@@ -232,80 +424,133 @@ impl TextSpan {
 
     
     pub fn color(&mut self, color: Color) -> &mut Self {
-        self.color = Some(color);
+        self.style.fg = Some(color);
         self
     }
     
     
     pub fn bg_color(&mut self, color: Color) -> &mut Self {
-        self.bg_color = Some(color);
+        self.style.bg = Some(color);
+        self
+    }
+
+
+    pub fn underline_color(&mut self, color: Color) -> &mut Self {
+        self.style.underline_color = Some(color);
         self
     }
 
     
     pub fn bold(&mut self) -> &mut Self {
-        self.styles.push(Style::Bold);
-        self
+        self.add_modifier(Modifier::Bold)
     }
     
     pub fn dim(&mut self) -> &mut Self {
-        self.styles.push(Style::Dim);
-        self
+        self.add_modifier(Modifier::Dim)
     }
     
     pub fn italic(&mut self) -> &mut Self {
-        self.styles.push(Style::Italic);
-        self
+        self.add_modifier(Modifier::Italic)
     }
     
     pub fn underlined(&mut self) -> &mut Self {
-        self.styles.push(Style::Underlined);
+        self.add_modifier(Modifier::Underlined)
+    }
+    
+    /// Underlines with the given shape, like a red `UnderlineStyle::Curly` line under a typo
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let typo = span!("teh", underline UnderlineStyle::Curly, underline_color Color::Red);
+    /// assert_eq!(typo.render(), "\x1b[4:3;58;5;1mteh\x1b[0m");
+    /// ```
+    pub fn underline(&mut self, shape: UnderlineStyle) -> &mut Self {
+        self.style = self.style.with_underline(shape);
         self
     }
     
     pub fn blink(&mut self) -> &mut Self {
-        self.styles.push(Style::Blink);
-        self
+        self.add_modifier(Modifier::Blink)
     }
     
     pub fn reverse_video(&mut self) -> &mut Self {
-        self.styles.push(Style::ReverseVideo);
-        self
+        self.add_modifier(Modifier::ReverseVideo)
     }
     
     pub fn concealed(&mut self) -> &mut Self {
-        self.styles.push(Style::Concealed);
-        self
+        self.add_modifier(Modifier::Concealed)
     }
     
     pub fn strikethrough(&mut self) -> &mut Self {
-        self.styles.push(Style::Strikethrough);
+        self.add_modifier(Modifier::Strikethrough)
+    }
+
+    pub fn add_modifier(&mut self, modifier: Modifier) -> &mut Self {
+        self.style = self.style.with_modifier(modifier);
+        self
+    }
+
+    /// Turns a modifier off, even if the component around the span turns it on
+    pub fn remove_modifier(&mut self, modifier: Modifier) -> &mut Self {
+        self.style = self.style.without_modifier(modifier);
+        self
+    }
+
+    /// Lays `style` over the style of the span, see `Style::patch`
+    pub fn style(&mut self, style: Style) -> &mut Self {
+        self.style = self.style.patch(style);
         self
     }
 
+    /// * Makes the span a hyperlink to `url`
+    ///
+    /// Terminals that support OSC 8 make it clickable. On other ones the runtime writes the URL after the text
+    /// (see `Ui::with_hyperlinks`).
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let docs = span!("docs", link "https://docs.rs/tuitui", underlined);
+    /// assert_eq!(docs.get_link(), Some("https://docs.rs/tuitui"));
+    /// ```
+    pub fn link(&mut self, url: &str) -> &mut Self {
+        self.link = (!url.is_empty()).then(|| url.to_string());
+        self
+    }
+
+    pub fn get_link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    pub fn get_style(&self) -> Style {
+        self.style
+    }
+
     pub fn render_plain(&self) -> String {
         self.content.clone()
     }
+
+    pub(crate) fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub(crate) fn same_style(&self, other: &TextSpan) -> bool {
+        self.style == other.style && self.link == other.link
+    }
+
+    /// The number of terminal columns taken by the widest line
+    pub fn width(&self) -> usize {
+        width::max_line_width(&self.content)
+    }
 }
 
 impl Component for TextSpan {
     
     fn render(&self) -> String {
-        let mut output = String::new();
-        
-        for style in &self.styles {
-            output.push_str(&style.ansi())
-        }
-        if let Some(color) = self.color {
-            output.push_str(&color.ansi_fg());
-        }
-        if let Some(bg) = self.bg_color {
-            output.push_str(&bg.ansi_bg());
-        }
-        
-        output.push_str(&self.content);
-        output.push_str("\x1b[0m");
-        output
+        let plain = Style::new();
+        let content = match &self.link {
+            Some(url) => format!("{}{}{}", ansi::hyperlink(url), self.content, ansi::hyperlink("")),
+            None => self.content.clone(),
+        };
+        format!("{}{}{}", self.style.sgr(&plain), content, plain.sgr(&self.style))
     }
 }
 
@@ -334,4 +579,49 @@ impl From<TextSpan> for Text {
         text.span_from(span);
         text
     }
-}
\ No newline at end of file
+}
+
+/// * Appends the spans of anything that turns into a `Text`
+///
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut status = Text::from("saved ") + span!("main.rs", bold);
+/// status += " (2 files)";
+/// assert_eq!(status.render_plain(), "saved main.rs (2 files)");
+/// ```
+impl<T: Into<Text>> Add<T> for Text {
+    type Output = Text;
+
+    fn add(mut self, other: T) -> Text {
+        self += other;
+        self
+    }
+}
+
+impl<T: Into<Text>> AddAssign<T> for Text {
+
+    fn add_assign(&mut self, other: T) {
+        self.spans.extend(other.into().spans);
+    }
+}
+
+impl Extend<TextSpan> for Text {
+
+    fn extend<I: IntoIterator<Item = TextSpan>>(&mut self, iter: I) {
+        self.spans.extend(iter);
+    }
+}
+
+impl Extend<Text> for Text {
+
+    fn extend<I: IntoIterator<Item = Text>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|text| *self += text);
+    }
+}
+
+impl FromIterator<TextSpan> for Text {
+
+    fn from_iter<I: IntoIterator<Item = TextSpan>>(iter: I) -> Self {
+        Self { spans: iter.into_iter().collect() }
+    }
+}
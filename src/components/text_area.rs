@@ -0,0 +1,703 @@
+use super::{Component, Modifier, Style, Text};
+use crate::inputs::{InputKey, Key};
+use crate::{theme::Theme, width};
+
+/// How many edits `undo` can go back
+const HISTORY_LIMIT: usize = 100;
+const TAB: &str = "    ";
+
+/// A line and a column, counted in grapheme clusters
+type Position = (usize, usize);
+
+/// Edits of the same kind made one after the other are undone together, like a typed word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    lines: Vec<String>,
+    cursor: Position,
+}
+
+/// A line on screen: a whole line, or part of one when soft wrap breaks it
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    line: usize,
+    start: usize,
+    end: usize,
+    /// Whether the row ends its line
+    last: bool,
+}
+
+/// * TextArea - an editor for multi-line text
+///
+/// Feed it keys from `App::update` with `handle_key`, and add it to the `Ui` with `Ui::text_area` every frame.
+/// It knows the usual bindings:
+///
+/// | Keys | Action |
+/// |---|---|
+/// | Arrows, Home, End, PageUp, PageDown | Move, with Ctrl+Left and Ctrl+Right jumping over words |
+/// | Shift with any of those | Select |
+/// | Ctrl+A | Select everything |
+/// | Ctrl+C, Ctrl+X, Ctrl+V | Copy, cut and paste |
+/// | Ctrl+Z, Ctrl+Y | Undo and redo |
+///
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut area = TextArea::new().with_size(20, 3);
+/// for c in "hello".chars() {
+///     area.handle_key(Key::from_char(c).unwrap());
+/// }
+/// area.handle_key(Key::new(InputKey::Enter));
+/// area.insert_str("world");
+/// assert_eq!(area.contents(), "hello\nworld");
+///
+/// area.undo();
+/// assert_eq!(area.contents(), "hello\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    cursor: Position,
+    /// The other end of the selection, which runs up to the cursor
+    anchor: Option<Position>,
+    /// The column up and down try to stay in, through lines too short for it
+    goal_column: Option<usize>,
+    /// The first row and column on screen
+    scroll: (usize, usize),
+    width: usize,
+    height: usize,
+    line_numbers: bool,
+    soft_wrap: bool,
+    focused: bool,
+    undo: Vec<State>,
+    redo: Vec<State>,
+    last_edit: Option<EditKind>,
+    clipboard: String,
+    /// Copied text the `Ui` hasn't sent to the system clipboard yet
+    copied: Option<String>,
+}
+
+impl Default for TextArea {
+
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            cursor: (0, 0),
+            anchor: None,
+            goal_column: None,
+            scroll: (0, 0),
+            width: 40,
+            height: 10,
+            line_numbers: false,
+            soft_wrap: false,
+            focused: true,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: None,
+            clipboard: String::new(),
+            copied: None,
+        }
+    }
+}
+
+fn graphemes(line: &str) -> Vec<&str> {
+    width::graphemes(line).collect()
+}
+
+fn grapheme_count(line: &str) -> usize {
+    width::graphemes(line).count()
+}
+
+/// The byte index of a column of a line
+fn byte_index(line: &str, column: usize) -> usize {
+    width::graphemes(line).take(column).map(str::len).sum()
+}
+
+fn is_space(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+impl TextArea {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+
+    pub fn with_contents(mut self, contents: &str) -> Self {
+        self.set_contents(contents); self
+    }
+
+
+    /// The number of columns and rows on screen, line numbers included
+    pub fn with_size(mut self, width: usize, height: usize) -> Self {
+        self.set_size(width, height); self
+    }
+
+
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self.scroll_to_cursor();
+        self
+    }
+
+
+    /// Breaks lines too long for the area between words, instead of scrolling sideways
+    pub fn with_soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self.scroll_to_cursor();
+        self
+    }
+
+
+    /// Only a focused area draws its cursor
+    pub fn with_focus(mut self, focused: bool) -> Self {
+        self.focused = focused; self
+    }
+
+
+    /// Replaces the whole text, clearing the history and putting the cursor at the end
+    pub fn set_contents(&mut self, contents: &str) {
+        let contents = contents.replace("\r\n", "\n").replace('\t', TAB);
+        self.lines = contents.split('\n').map(str::to_string).collect();
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+        self.anchor = None;
+        let last = self.lines.len() - 1;
+        self.move_to((last, grapheme_count(&self.lines[last])), false);
+        self.scroll_to_cursor();
+    }
+
+
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self.scroll_to_cursor();
+    }
+
+
+    pub fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+
+    pub fn contents(&self) -> String {
+        self.lines.join("\n")
+    }
+
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+
+    /// The line and column of the cursor, both counted from 0. Columns count grapheme clusters
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+
+    pub fn set_cursor(&mut self, line: usize, column: usize) {
+        let line = line.min(self.lines.len() - 1);
+        let column = column.min(grapheme_count(&self.lines[line]));
+        self.move_to((line, column), false);
+        self.scroll_to_cursor();
+    }
+
+
+    /// The selected text, if anything is selected
+    pub fn selection(&self) -> Option<String> {
+        let (start, end) = self.selection_range()?;
+        let mut selected = String::new();
+        for line in start.0..=end.0 {
+            let text = &self.lines[line];
+            let from = if line == start.0 { byte_index(text, start.1) } else { 0 };
+            let to = if line == end.0 { byte_index(text, end.1) } else { text.len() };
+            if line > start.0 {
+                selected.push('\n');
+            }
+            selected.push_str(&text[from..to]);
+        }
+        Some(selected)
+    }
+
+
+    pub fn select_all(&mut self) {
+        let last = self.lines.len() - 1;
+        self.anchor = Some((0, 0));
+        self.cursor = (last, grapheme_count(&self.lines[last]));
+        self.scroll_to_cursor();
+    }
+
+
+    /// What was last copied or cut, and what `paste` inserts
+    pub fn clipboard(&self) -> &str {
+        &self.clipboard
+    }
+
+
+    /// Text copied since the last call, to send to the system clipboard. `Ui::text_area` takes care of it
+    pub fn take_copied(&mut self) -> Option<String> {
+        self.copied.take()
+    }
+
+
+    /// Types text at the cursor, replacing the selection
+    pub fn insert_str(&mut self, text: &str) {
+        self.checkpoint(EditKind::Other);
+        self.insert(text);
+        self.scroll_to_cursor();
+    }
+
+
+    pub fn copy(&mut self) {
+        if let Some(selected) = self.selection() {
+            self.clipboard = selected.clone();
+            self.copied = Some(selected);
+        }
+    }
+
+
+    pub fn cut(&mut self) {
+        if self.selection_range().is_some() {
+            self.copy();
+            self.checkpoint(EditKind::Other);
+            self.delete_selection();
+            self.scroll_to_cursor();
+        }
+    }
+
+
+    pub fn paste(&mut self) {
+        if !self.clipboard.is_empty() {
+            let clipboard = self.clipboard.clone();
+            self.insert_str(&clipboard);
+        }
+    }
+
+
+    /// Goes back to before the last edit. Returns `false` when there's nothing left to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(state) = self.undo.pop() else {
+            return false;
+        };
+        let current = self.restore(state);
+        self.redo.push(current);
+        true
+    }
+
+
+    pub fn redo(&mut self) -> bool {
+        let Some(state) = self.redo.pop() else {
+            return false;
+        };
+        let current = self.restore(state);
+        self.undo.push(current);
+        true
+    }
+
+
+    /// * Edits the text or moves the cursor as the key says. Returns `false` for keys the area doesn't use
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        let extend = key.shift();
+        let handled = match key.key {
+            InputKey::LeftArrow if key.ctrl() => self.move_to(self.word_left(), extend),
+            InputKey::RightArrow if key.ctrl() => self.move_to(self.word_right(), extend),
+            InputKey::LeftArrow => match self.selection_range() {
+                Some((start, _)) if !extend => self.move_to(start, false),
+                _ => self.move_to(self.left(), extend),
+            },
+            InputKey::RightArrow => match self.selection_range() {
+                Some((_, end)) if !extend => self.move_to(end, false),
+                _ => self.move_to(self.right(), extend),
+            },
+            InputKey::UpArrow => self.move_rows(-1, extend),
+            InputKey::DownArrow => self.move_rows(1, extend),
+            InputKey::PageUp => self.move_rows(-(self.height as isize), extend),
+            InputKey::PageDown => self.move_rows(self.height as isize, extend),
+            InputKey::Home if key.ctrl() => self.move_to((0, 0), extend),
+            InputKey::End if key.ctrl() => {
+                let last = self.lines.len() - 1;
+                self.move_to((last, grapheme_count(&self.lines[last])), extend)
+            }
+            InputKey::Home => self.move_to((self.cursor.0, 0), extend),
+            InputKey::End => self.move_to((self.cursor.0, grapheme_count(&self.lines[self.cursor.0])), extend),
+
+            InputKey::A if key.ctrl() => {
+                self.select_all();
+                true
+            }
+            InputKey::C if key.ctrl() => {
+                self.copy();
+                true
+            }
+            InputKey::X if key.ctrl() => {
+                self.cut();
+                true
+            }
+            InputKey::V if key.ctrl() => {
+                self.paste();
+                true
+            }
+            InputKey::Z if key.ctrl() && key.shift() => self.redo(),
+            InputKey::Z if key.ctrl() => self.undo(),
+            InputKey::Y if key.ctrl() => self.redo(),
+
+            InputKey::Backspace => self.delete_to(self.left()),
+            InputKey::Delete => self.delete_to(self.right()),
+            InputKey::Enter => {
+                self.insert_str("\n");
+                true
+            }
+            InputKey::Tab => {
+                self.insert_str(TAB);
+                true
+            }
+            _ if key.ctrl() || key.alt() => false,
+            _ => match key.character.or_else(|| key.key.to_char()) {
+                Some(c) => {
+                    // A space ends the word being typed, so undo takes words back one at a time
+                    self.checkpoint(if c == ' ' { EditKind::Other } else { EditKind::Insert });
+                    self.insert(&c.to_string());
+                    true
+                }
+                None => false,
+            },
+        };
+        self.scroll_to_cursor();
+        handled
+    }
+
+
+    /// * Lays the area out like it's rendered: exactly as many rows as its height, scrolled to show the cursor
+    pub fn to_text(&self, theme: &Theme) -> Text {
+        let rows = self.rows();
+        let digits = self.lines.len().to_string().len();
+        let columns = self.text_width();
+        let selection = self.selection_range();
+        let selected_style = Style::new().with_bg(theme.secondary);
+        let cursor_style = Style::from(Modifier::ReverseVideo);
+
+        let mut text = Text::new();
+        for i in 0..self.height {
+            if i > 0 {
+                text.span("\n");
+            }
+            let Some(row) = rows.get(self.scroll.0 + i) else {
+                continue;
+            };
+            if self.line_numbers {
+                let number = if row.start == 0 { (row.line + 1).to_string() } else { String::new() };
+                let color = if row.line == self.cursor.0 { theme.primary } else { theme.muted };
+                text.span(&format!("{:>digits$} ", number)).color(color);
+                text.span("│ ").style(Style { fg: theme.border, ..Style::new() });
+            }
+
+            let selected = |column: usize| selection.is_some_and(|(start, end)| (row.line, column) >= start && (row.line, column) < end);
+            let mut line = Text::new();
+            for (column, grapheme) in graphemes(&self.lines[row.line]).into_iter().enumerate().take(row.end).skip(row.start) {
+                let span = line.span(grapheme);
+                if selected(column) {
+                    span.style(selected_style);
+                }
+                if self.focused && self.cursor == (row.line, column) {
+                    span.style(cursor_style);
+                }
+            }
+            // The cursor after the last character, and the line break of a selection, take a blank cell
+            let at_end = self.focused && self.cursor == (row.line, row.end) && row.last;
+            let break_selected = row.last && selected(row.end) && row.line + 1 < self.lines.len();
+            if at_end || break_selected {
+                let span = line.span(" ");
+                if break_selected {
+                    span.style(selected_style);
+                }
+                if at_end {
+                    span.style(cursor_style);
+                }
+            }
+
+            if self.soft_wrap {
+                text += line;
+            } else {
+                text += line.slice(self.scroll.1..self.scroll.1 + columns);
+            }
+        }
+        text
+    }
+
+
+    /// The columns left for the text next to the line numbers
+    fn text_width(&self) -> usize {
+        let gutter = if self.line_numbers { self.lines.len().to_string().len() + 3 } else { 0 };
+        self.width.saturating_sub(gutter).max(1)
+    }
+
+
+    /// Splits the lines into the rows they take on screen
+    fn rows(&self) -> Vec<Row> {
+        let limit = self.text_width();
+        let mut rows = Vec::new();
+        for (line, text) in self.lines.iter().enumerate() {
+            let graphemes = graphemes(text);
+            let mut start = 0;
+            if self.soft_wrap {
+                let mut used = 0;
+                let mut break_after = None;
+                for (column, grapheme) in graphemes.iter().enumerate() {
+                    let columns = width::grapheme_width(grapheme);
+                    if used + columns > limit && column > start {
+                        let end = break_after.filter(|b| *b > start).unwrap_or(column);
+                        rows.push(Row { line, start, end, last: false });
+                        start = end;
+                        used = graphemes[start..column].iter().map(|g| width::grapheme_width(g)).sum();
+                        break_after = None;
+                    }
+                    used += columns;
+                    if is_space(grapheme) {
+                        break_after = Some(column + 1);
+                    }
+                }
+            }
+            rows.push(Row { line, start, end: graphemes.len(), last: true });
+        }
+        rows
+    }
+
+
+    /// The row the cursor is on, and the screen column within it
+    fn cursor_row(&self, rows: &[Row]) -> (usize, usize) {
+        let (line, column) = self.cursor;
+        let index = rows
+            .iter()
+            .position(|r| r.line == line && r.start <= column && (column < r.end || r.last))
+            .unwrap_or(0);
+        let row = rows[index];
+        let x = graphemes(&self.lines[line])[row.start..column].iter().map(|g| width::grapheme_width(g)).sum();
+        (index, x)
+    }
+
+
+    fn move_to(&mut self, position: Position, extend: bool) -> bool {
+        if extend {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = position;
+        self.goal_column = None;
+        self.last_edit = None;
+        true
+    }
+
+
+    /// Moves up or down by rows of the screen, keeping to the same column where the rows are long enough
+    fn move_rows(&mut self, delta: isize, extend: bool) -> bool {
+        let rows = self.rows();
+        let (index, x) = self.cursor_row(&rows);
+        let goal = self.goal_column.unwrap_or(x);
+        let target = index as isize + delta;
+        if target < 0 {
+            return self.move_to((0, 0), extend);
+        }
+        if target as usize >= rows.len() {
+            let last = self.lines.len() - 1;
+            return self.move_to((last, grapheme_count(&self.lines[last])), extend);
+        }
+
+        let row = rows[target as usize];
+        let graphemes = graphemes(&self.lines[row.line]);
+        let mut column = row.start;
+        let mut used = 0;
+        while column < row.end && used + width::grapheme_width(graphemes[column]) <= goal {
+            used += width::grapheme_width(graphemes[column]);
+            column += 1;
+        }
+        // The end of a wrapped row is the start of the next one
+        if !row.last && column == row.end {
+            column = row.end.saturating_sub(1).max(row.start);
+        }
+        self.move_to((row.line, column), extend);
+        self.goal_column = Some(goal);
+        true
+    }
+
+
+    fn left(&self) -> Position {
+        match self.cursor {
+            (0, 0) => (0, 0),
+            (line, 0) => (line - 1, grapheme_count(&self.lines[line - 1])),
+            (line, column) => (line, column - 1),
+        }
+    }
+
+
+    fn right(&self) -> Position {
+        let (line, column) = self.cursor;
+        if column < grapheme_count(&self.lines[line]) {
+            (line, column + 1)
+        } else if line + 1 < self.lines.len() {
+            (line + 1, 0)
+        } else {
+            self.cursor
+        }
+    }
+
+
+    /// The start of the word before the cursor
+    fn word_left(&self) -> Position {
+        let (line, column) = self.cursor;
+        if column == 0 {
+            return self.left();
+        }
+        let graphemes = graphemes(&self.lines[line]);
+        let mut column = column;
+        while column > 0 && is_space(graphemes[column - 1]) {
+            column -= 1;
+        }
+        while column > 0 && !is_space(graphemes[column - 1]) {
+            column -= 1;
+        }
+        (line, column)
+    }
+
+
+    /// The end of the word after the cursor
+    fn word_right(&self) -> Position {
+        let (line, column) = self.cursor;
+        let graphemes = graphemes(&self.lines[line]);
+        if column == graphemes.len() {
+            return self.right();
+        }
+        let mut column = column;
+        while column < graphemes.len() && is_space(graphemes[column]) {
+            column += 1;
+        }
+        while column < graphemes.len() && !is_space(graphemes[column]) {
+            column += 1;
+        }
+        (line, column)
+    }
+
+
+    /// The start and end of the selection, in the order they come in the text
+    fn selection_range(&self) -> Option<(Position, Position)> {
+        let anchor = self.anchor.filter(|a| *a != self.cursor)?;
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+
+    /// Saves the text for `undo`, unless the edit continues the one before
+    fn checkpoint(&mut self, kind: EditKind) {
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            self.undo.push(State { lines: self.lines.clone(), cursor: self.cursor });
+            if self.undo.len() > HISTORY_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last_edit = Some(kind);
+    }
+
+
+    fn restore(&mut self, state: State) -> State {
+        let current = State { lines: std::mem::replace(&mut self.lines, state.lines), cursor: self.cursor };
+        self.cursor = state.cursor;
+        self.anchor = None;
+        self.goal_column = None;
+        self.last_edit = None;
+        self.scroll_to_cursor();
+        current
+    }
+
+
+    /// Removes the selection, or the text between the cursor and `position` when there's none. Returns `false`
+    /// when there's nothing to remove, like Backspace at the very start, which then leaves no step to undo
+    fn delete_to(&mut self, position: Position) -> bool {
+        if self.selection_range().is_none() {
+            self.anchor = Some(position);
+        }
+        if self.selection_range().is_some() {
+            self.checkpoint(EditKind::Delete);
+        }
+        self.delete_selection()
+    }
+
+
+    /// Removes the selected text. Returns `false` if nothing was selected
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection_range() else {
+            self.anchor = None;
+            return false;
+        };
+        let head = &self.lines[start.0][..byte_index(&self.lines[start.0], start.1)];
+        let tail = &self.lines[end.0][byte_index(&self.lines[end.0], end.1)..];
+        let joined = format!("{}{}", head, tail);
+        self.lines.splice(start.0..=end.0, [joined]);
+        self.cursor = start;
+        self.anchor = None;
+        true
+    }
+
+
+    fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let text = text.replace("\r\n", "\n").replace('\t', TAB);
+        let (line, column) = self.cursor;
+        let tail = {
+            let index = byte_index(&self.lines[line], column);
+            self.lines[line].split_off(index)
+        };
+
+        let mut parts = text.split('\n');
+        self.lines[line].push_str(parts.next().unwrap_or(""));
+        let mut current = line;
+        for part in parts {
+            current += 1;
+            self.lines.insert(current, part.to_string());
+        }
+        let column = grapheme_count(&self.lines[current]);
+        self.lines[current].push_str(&tail);
+        self.cursor = (current, column);
+        self.goal_column = None;
+    }
+
+
+    /// Scrolls just enough to bring the cursor on screen
+    fn scroll_to_cursor(&mut self) {
+        let rows = self.rows();
+        let (row, x) = self.cursor_row(&rows);
+        if row < self.scroll.0 {
+            self.scroll.0 = row;
+        } else if row >= self.scroll.0 + self.height {
+            self.scroll.0 = row + 1 - self.height;
+        }
+
+        let columns = self.text_width();
+        if self.soft_wrap {
+            self.scroll.1 = 0;
+        } else if x < self.scroll.1 {
+            self.scroll.1 = x;
+        } else if x >= self.scroll.1 + columns {
+            self.scroll.1 = x + 1 - columns;
+        }
+    }
+}
+
+impl Component for TextArea {
+
+    fn render(&self) -> String {
+        self.to_text(&Theme::default()).render()
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        self.to_text(theme).render()
+    }
+}
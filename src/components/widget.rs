@@ -1,74 +1,114 @@
 use super::Component;
-use super::Text;
+use super::{Alignment, Ellipsis, Style, Text};
+use super::text::Color;
+use crate::{theme::Theme, width};
 
+/// * Widget - a box drawn around its contents, with an optional title
+///
+/// Without `with_width` the box fits its contents and title. With it, the contents are aligned within that width and
+/// cut at the border when they're wider:
+/// ```
+/// # use tuitui::prelude::*;
+/// let widget = Widget::new()
+///     .with_contents("Hi".into())
+///     .with_width(8)
+///     .with_height(3)
+///     .with_alignment(Alignment::Center)
+///     .build();
+/// assert_eq!(widget.render(), "┌──────┐\n│  Hi  │\n└──────┘");
+///
+/// let fitted = Widget::new().with_contents("Hi".into()).with_height(3).build();
+/// assert_eq!(fitted.render(), "┌──┐\n│Hi│\n└──┘");
+/// ```
 #[derive(Debug, Clone)]
 pub struct Widget {
-    pub style: WidgetStyle,
+    /// The border characters, or the ones of the theme when `None`
+    pub style: Option<WidgetStyle>,
+    /// The width of the box, borders included
     pub width: u16,
     pub height: u16,
-    pub contents: Text
+    pub contents: Text,
+    /// Inherited by every span of the contents
+    pub content_style: Style,
+    pub alignment: Alignment,
+    /// Shown in the top border, cut short with `…` if the widget is too narrow
+    pub title: Option<Text>
 }
 
 impl Component for Widget {
     
     fn render(&self) -> String {
-        let binding = self.contents.render_plain();
-        let lines = binding.lines().collect::<Vec<_>>();
+        let style = self.style.clone().unwrap_or_else(WidgetStyle::tuitui_classic);
+        self.render_with(&style, None, None)
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        let style = self.style.as_ref().unwrap_or(&theme.border_style);
+        self.render_with(style, theme.border, theme.heading_style.fg)
+    }
+}
+
+/// Wraps part of a border in a color, if there is one
+fn paint(content: String, color: Option<Color>) -> String {
+    match color {
+        Some(color) => format!("{}{}\x1b[0m", color.ansi_fg(), content),
+        None => content,
+    }
+}
+
+impl Widget {
+    
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> WidgetBuilder {
+        WidgetBuilder::new()
+    }
 
-        let width = lines
-            .iter()
-            .max_by_key(|c| c.len())
-            .unwrap()
-            .len()
-            .min(self.width.into());
+
+    fn render_with(&self, style: &WidgetStyle, border_color: Option<Color>, title_color: Option<Color>) -> String {
+        let width = (self.width as usize).saturating_sub(2);
+        let aligned = self.contents.align(self.alignment, width);
+        let lines = aligned.lines();
 
         let mut buffer = String::new();
-        buffer.push(self.style.top_left);
-        for _ in 0..width {
-            buffer.push(self.style.top_horizontal);
+        let title = self.title
+            .as_ref()
+            .filter(|_| width > 4)
+            .map(|t| t.ellipsize(width - 4, Ellipsis::End));
+        let mut border = width;
+        if let Some(title) = title {
+            let title = title_color.map_or(title.clone(), |c| title.inherit(Style::new().with_fg(c)));
+            buffer.push_str(&paint(format!("{}{} ", style.top_left, style.top_horizontal), border_color));
+            buffer.push_str(&title.render());
+            buffer.push(' ');
+            border -= title.width() + 3;
+        } else {
+            buffer.push_str(&paint(style.top_left.to_string(), border_color));
         }
-        buffer.push(self.style.top_right);
+        let top = std::iter::repeat_n(style.top_horizontal, border).collect::<String>();
+        buffer.push_str(&paint(format!("{}{}", top, style.top_right), border_color));
         buffer.push('\n');
 
-        for l in 0..(self.height - 2) {
-            buffer.push(self.style.left_vertical);
-            for i in 0..width {
-                let binding = lines
-                    .get(l as usize)
-                    .unwrap_or(&" ")
-                ;
-                let binding = 
-                    binding
-                    .chars()
-                    .collect::<Vec<_>>();
-                let c = binding
-                    .get(i as usize)
-                    .unwrap_or(&' ')
-                ;
-                buffer.push(*c)
-            }
-            buffer.push(self.style.right_vertical);
+        for l in 0..self.height.saturating_sub(2) {
+            let mut line = lines
+                .get(l as usize)
+                .map_or_else(Text::new, |items| aligned.join_lines(vec![(0, items.clone())]));
+            line.truncate(width);
+            buffer.push_str(&paint(style.left_vertical.to_string(), border_color));
+            buffer.push_str(&line.inherit(self.content_style).render());
+            buffer.push_str(&" ".repeat(width - line.width()));
+            buffer.push_str(&paint(style.right_vertical.to_string(), border_color));
             buffer.push('\n');
         }
         
-        buffer.push(self.style.bottom_left);
-        for _ in 0..width {
-            buffer.push(self.style.bottom_horizontal);
-        }
-        buffer.push(self.style.bottom_right);
+        let bottom = std::iter::repeat_n(style.bottom_horizontal, width).collect::<String>();
+        buffer.push_str(&paint(format!("{}{}{}", style.bottom_left, bottom, style.bottom_right), border_color));
         
         buffer
     }
 }
 
-impl Widget {
-    
-    pub fn new() -> WidgetBuilder {
-        WidgetBuilder::new()
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WidgetStyle {
     pub top_left: char,
     pub top_right: char, 
@@ -139,6 +179,7 @@ impl WidgetStyle {
     }
 
     
+    #[allow(clippy::too_many_arguments)]
     pub fn custom(
         top_left: char,
         top_right: char, 
@@ -223,9 +264,16 @@ impl WidgetStyle {
     }
 
     
+    /// Picks a border style by name, falling back to the classic one for names it doesn't know
     pub fn from_name(name: &str) -> Self {
+        Self::try_from_name(name).unwrap_or_else(Self::tuitui_classic)
+    }
+
+
+    /// Like `from_name`, but `None` for names it doesn't know
+    pub fn try_from_name(name: &str) -> Option<Self> {
         let n = name.to_lowercase();
-        match n.strip_prefix("tuitui ").unwrap_or(&n) {
+        let style = match n.strip_prefix("tuitui ").unwrap_or(&n) {
             "classic" | "default" => Self::tuitui_classic(),
             "heavy box" | "thick box" | "bold box" => Self::tuitui_heavy_box(),
             "rounded" | "smooth" => Self::tuitui_rounded(),
@@ -237,14 +285,19 @@ impl WidgetStyle {
             "none" | "no border" | "no-border" => Self::no_border(),
             "dotted" | "dot" => Self::dotted(),
             "dashed" | "dash" => Self::dashed(),
-            _ => Self::tuitui_classic()
-        }
+            _ => return None,
+        };
+        Some(style)
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct WidgetBuilder {
-    widget: Widget
+    widget: Widget,
+    /// Whether `with_width` was called, otherwise the box fits its contents
+    sized: bool,
+    /// Writes link targets after the link text, for terminals without hyperlinks
+    inline_links: bool,
 }
 
 impl WidgetBuilder {
@@ -252,19 +305,38 @@ impl WidgetBuilder {
     fn new() -> Self {
         Self {
             widget: Widget {
-                style: WidgetStyle::from_name("Tuitui Classic"),
+                style: None,
                 width: 10,
                 height: 10,
                 contents: Text {
                     spans: Vec::new()
-                }
-            }
+                },
+                content_style: Style::new(),
+                alignment: Alignment::Left,
+                title: None
+            },
+            sized: false,
+            inline_links: false,
         }
     }
 
+
+    /// Set by the `Ui` for terminals without hyperlinks, see `Text::inline_links`
+    pub(crate) fn with_inline_links(mut self, inline_links: bool) -> Self {
+        self.inline_links = inline_links; self
+    }
+
+
+    fn links(&self, text: Text) -> Text {
+        if self.inline_links { text.inline_links() } else { text }
+    }
+
     
+    /// Sets the width of the box, borders included. Contents wider than that are cut off
     pub fn with_width(mut self, w: u16) -> Self {
-        self.widget.width = w.max(2); self
+        self.widget.width = w.max(2);
+        self.sized = true;
+        self
     }
 
     
@@ -274,28 +346,62 @@ impl WidgetBuilder {
 
     
     pub fn with_style(mut self, style: WidgetStyle) -> Self {
-        self.widget.style = style; self
+        self.widget.style = Some(style); self
     }
 
     
-    pub fn with_contents(mut self, contents: Text) -> Self {
+    pub fn with_contents(self, contents: Text) -> Self {
+        let contents = self.links(contents);
+        self.set_contents(contents)
+    }
+
+
+    fn set_contents(mut self, contents: Text) -> Self {
         self.widget.contents = contents.clone();
 
-        let binding = contents.render();
+        let binding = contents.render_plain();
         let lines = binding.lines().collect::<Vec<_>>();
-        let largest = lines.iter().max_by_key(|s| s.len()).unwrap_or(&"").len();
-        if largest > self.widget.width as usize - 2 {
-            self.widget.width = contents.render().len() as u16 + 2;
+        let largest = width::max_line_width(&binding);
+        if !self.sized && largest > (self.widget.width as usize).saturating_sub(2) {
+            self.widget.width = largest as u16 + 2;
         }
 
-        if lines.len() > self.widget.height as usize - 2 {
-            self.widget.width = contents.render().len() as u16 + 2;
+        if lines.len() > (self.widget.height as usize).saturating_sub(2) {
+            self.widget.height = lines.len() as u16 + 2;
         }
         self
     }
 
     
-    pub fn build(self) -> Widget {
+    pub fn with_title(mut self, title: Text) -> Self {
+        self.widget.title = Some(self.links(title)); self
+    }
+
+
+    pub fn with_content_style(mut self, style: Style) -> Self {
+        self.widget.content_style = style; self
+    }
+
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.widget.alignment = alignment; self
+    }
+
+
+    /// Sets the contents, wrapped between words to fit inside the current width
+    pub fn with_wrapped_contents(self, contents: Text) -> Self {
+        let inner = (self.widget.width as usize).saturating_sub(2);
+        let contents = self.links(contents).wrap(inner);
+        self.set_contents(contents)
+    }
+
+    
+    pub fn build(mut self) -> Widget {
+        if !self.sized {
+            // A title takes a border character and a space on both sides
+            let title_width = self.widget.title.as_ref().map_or(0, |t| t.width() + 4);
+            self.widget.width = (self.widget.contents.width().max(title_width) + 2) as u16;
+        }
         self.widget
     }
 }
\ No newline at end of file
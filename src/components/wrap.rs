@@ -0,0 +1,289 @@
+use super::text::{Text, TextSpan};
+use crate::width;
+
+/// * Where lines may be broken when wrapping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapMode {
+    /// Break between words, and only split words longer than a whole line
+    #[default]
+    Word,
+    /// Break anywhere, filling every line completely
+    Char,
+}
+
+/// * Options for `Text::wrap_with`
+///
+/// ```
+/// # use tuitui::prelude::*;
+/// let text: Text = "a well-known fact about penguins".into();
+/// let wrapped = text.wrap_with(12, WrapOptions::new().with_indent(2));
+/// assert_eq!(wrapped.render_plain(), "a well-known\n  fact about\n  penguins");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrapOptions {
+    pub mode: WrapMode,
+    /// Spaces put in front of every line after the first one of a paragraph
+    pub indent: usize,
+    /// Removes whitespace at the start and end of every wrapped line
+    pub trim: bool,
+    /// Allows breaking after the hyphen of a hyphenated word
+    pub break_hyphens: bool,
+}
+
+impl Default for WrapOptions {
+
+    fn default() -> Self {
+        Self {
+            mode: WrapMode::Word,
+            indent: 0,
+            trim: true,
+            break_hyphens: true,
+        }
+    }
+}
+
+impl WrapOptions {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+
+    pub fn with_mode(mut self, mode: WrapMode) -> Self {
+        self.mode = mode; self
+    }
+
+
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent; self
+    }
+
+
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim; self
+    }
+
+
+    pub fn with_hyphen_breaks(mut self, break_hyphens: bool) -> Self {
+        self.break_hyphens = break_hyphens; self
+    }
+}
+
+/// A grapheme cluster and the index of the span it came from
+pub(super) type Item<'a> = (&'a str, usize);
+
+pub(super) fn is_space(item: &Item) -> bool {
+    item.0.chars().all(char::is_whitespace)
+}
+
+pub(super) fn items_width(items: &[Item]) -> usize {
+    items.iter().map(|(g, _)| width::grapheme_width(g)).sum()
+}
+
+/// Lays the lines of one paragraph out, one `Vec` per output line
+struct Wrapper<'a> {
+    lines: Vec<Vec<Item<'a>>>,
+    current: Vec<Item<'a>>,
+    current_width: usize,
+    width: usize,
+    indent: usize,
+}
+
+impl<'a> Wrapper<'a> {
+
+    /// How many columns the current line can hold, after the hanging indent
+    fn limit(&self) -> usize {
+        if self.lines.is_empty() {
+            self.width
+        } else {
+            self.width.saturating_sub(self.indent).max(1)
+        }
+    }
+
+
+    fn fits(&self, columns: usize) -> bool {
+        self.current_width + columns <= self.limit()
+    }
+
+
+    fn push(&mut self, item: Item<'a>) {
+        self.current_width += width::grapheme_width(item.0);
+        self.current.push(item);
+    }
+
+
+    fn break_line(&mut self) {
+        self.lines.push(std::mem::take(&mut self.current));
+        self.current_width = 0;
+    }
+
+
+    /// Adds graphemes one by one, breaking whenever the line is full
+    fn push_chars(&mut self, items: &[Item<'a>], trim: bool) {
+        for item in items {
+            if !self.current.is_empty() && !self.fits(width::grapheme_width(item.0)) {
+                self.break_line();
+            }
+            if trim && self.current.is_empty() && !self.lines.is_empty() && is_space(item) {
+                continue;
+            }
+            self.push(*item);
+        }
+    }
+
+
+    fn finish(mut self, trim: bool) -> Vec<Vec<Item<'a>>> {
+        self.break_line();
+        if trim {
+            for line in &mut self.lines {
+                while line.last().is_some_and(is_space) {
+                    line.pop();
+                }
+                let start = line.iter().position(|i| !is_space(i)).unwrap_or(line.len());
+                line.drain(..start);
+            }
+        }
+        self.lines
+    }
+}
+
+/// Splits a line into runs of whitespace and words, ending a word after each of its hyphens if allowed
+fn tokens<'a>(items: &[Item<'a>], break_hyphens: bool) -> Vec<Vec<Item<'a>>> {
+    let mut tokens: Vec<Vec<Item>> = Vec::new();
+    let mut split_next = false;
+
+    for item in items {
+        match tokens.last_mut() {
+            Some(token) if !split_next && is_space(&token[0]) == is_space(item) => token.push(*item),
+            _ => tokens.push(vec![*item]),
+        }
+        let token = tokens.last().expect("a token was just pushed");
+        split_next = break_hyphens && item.0 == "-" && token.iter().any(|i| i.0 != "-");
+    }
+    tokens
+}
+
+fn wrap_line<'a>(items: &[Item<'a>], width: usize, options: WrapOptions) -> Vec<Vec<Item<'a>>> {
+    let mut wrapper = Wrapper {
+        lines: Vec::new(),
+        current: Vec::new(),
+        current_width: 0,
+        width: width.max(1),
+        indent: options.indent,
+    };
+
+    if options.mode == WrapMode::Char {
+        wrapper.push_chars(items, options.trim);
+        return wrapper.finish(options.trim);
+    }
+
+    let mut pending: Vec<Item> = Vec::new();
+    for token in tokens(items, options.break_hyphens) {
+        if is_space(&token[0]) {
+            pending.extend(token);
+            continue;
+        }
+
+        let token_width = items_width(&token);
+        let space_width = items_width(&pending);
+        if wrapper.fits(space_width + token_width) {
+            pending.drain(..).for_each(|i| wrapper.push(i));
+            token.into_iter().for_each(|i| wrapper.push(i));
+            continue;
+        }
+
+        // Whitespace at a break is dropped
+        pending.clear();
+        if !wrapper.current.is_empty() {
+            wrapper.break_line();
+        }
+        if wrapper.fits(token_width) {
+            token.into_iter().for_each(|i| wrapper.push(i));
+        } else {
+            wrapper.push_chars(&token, false);
+        }
+    }
+    if !options.trim && wrapper.fits(items_width(&pending)) {
+        pending.into_iter().for_each(|i| wrapper.push(i));
+    }
+
+    wrapper.finish(options.trim)
+}
+
+impl Text {
+
+    /// * Wraps the text between words to lines of at most `width` columns, keeping the styles of every span
+    ///
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let text = text!(span!("tuitui ", bold), span!("wraps long lines"));
+    /// assert_eq!(text.wrap(10).render_plain(), "tuitui\nwraps long\nlines");
+    /// ```
+    pub fn wrap(&self, width: usize) -> Text {
+        self.wrap_with(width, WrapOptions::default())
+    }
+
+
+    pub fn wrap_with(&self, width: usize, options: WrapOptions) -> Text {
+        let mut lines = Vec::new();
+        for paragraph in self.lines() {
+            for (i, line) in wrap_line(&paragraph, width, options).into_iter().enumerate() {
+                let indent = if i > 0 { options.indent } else { 0 };
+                lines.push((indent, line));
+            }
+        }
+        self.join_lines(lines)
+    }
+
+
+    /// Splits the text into lines of grapheme clusters, each tagged with the index of its span
+    pub(super) fn lines(&self) -> Vec<Vec<Item<'_>>> {
+        let mut lines: Vec<Vec<Item>> = vec![Vec::new()];
+        for (index, span) in self.spans.iter().enumerate() {
+            for grapheme in width::graphemes(span.content()) {
+                match grapheme {
+                    "\n" | "\r\n" => lines.push(Vec::new()),
+                    g => lines.last_mut().expect("there is always a line").push((g, index)),
+                }
+            }
+        }
+        lines
+    }
+
+
+    /// Joins lines made by `lines` back into a `Text`, putting unstyled padding in front of each one
+    pub(super) fn join_lines(&self, lines: Vec<(usize, Vec<Item>)>) -> Text {
+        let mut text = Text::new();
+        for (i, (padding, line)) in lines.into_iter().enumerate() {
+            let newline = if i > 0 { "\n" } else { "" };
+            if i > 0 || padding > 0 {
+                text.span(&format!("{}{}", newline, " ".repeat(padding)));
+            }
+
+            let mut current: Option<(usize, String)> = None;
+            for (grapheme, index) in line {
+                match &mut current {
+                    Some((span, content)) if *span == index => content.push_str(grapheme),
+                    _ => {
+                        if let Some((span, content)) = current.take() {
+                            text.span_from(self.restyled(span, &content));
+                        }
+                        current = Some((index, grapheme.to_string()));
+                    }
+                }
+            }
+            if let Some((span, content)) = current {
+                text.span_from(self.restyled(span, &content));
+            }
+        }
+        text
+    }
+
+
+    /// A copy of one of the spans holding other content
+    fn restyled(&self, index: usize, content: &str) -> TextSpan {
+        let mut span = self.spans[index].clone();
+        span.contents(content);
+        span
+    }
+}
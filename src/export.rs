@@ -0,0 +1,259 @@
+use crate::{buffer::{Buffer, Cell}, components::{text::Color, Component, Modifier, Text, UnderlineStyle}, ui::Ui};
+
+const DEFAULT_FG: Color = Color::White;
+const DEFAULT_BG: Color = Color::Black;
+const FONT_SIZE: f32 = 14.0;
+const CELL_WIDTH: f32 = 8.4;
+const CELL_HEIGHT: f32 = 18.0;
+const FONT_FAMILY: &str = "ui-monospace, 'Cascadia Mono', 'DejaVu Sans Mono', Menlo, Consolas, monospace";
+
+/// The colors a cell is actually painted with, after reverse video and concealment
+fn painted_colors(cell: &Cell) -> (Color, Color) {
+    let fg = cell.fg.unwrap_or(DEFAULT_FG);
+    let bg = cell.bg.unwrap_or(DEFAULT_BG);
+    let (fg, bg) = if cell.modifiers.contains(Modifier::ReverseVideo) { (bg, fg) } else { (fg, bg) };
+    if cell.modifiers.contains(Modifier::Concealed) { (bg, bg) } else { (fg, bg) }
+}
+
+fn text_decoration(cell: &Cell) -> Option<String> {
+    let decorations = [
+        (Modifier::Underlined, "underline"),
+        (Modifier::Strikethrough, "line-through"),
+        (Modifier::Blink, "blink"),
+    ]
+    .iter()
+    .filter(|(m, _)| cell.modifiers.contains(*m))
+    .map(|(_, d)| *d)
+    .collect::<Vec<_>>();
+
+    (!decorations.is_empty()).then(|| decorations.join(" "))
+}
+
+/// The CSS `text-decoration-style` and `text-decoration-color` of an underline that isn't a plain one
+fn underline_details(cell: &Cell) -> Vec<(&'static str, String)> {
+    let mut details = Vec::new();
+    if !cell.modifiers.contains(Modifier::Underlined) {
+        return details;
+    }
+    let shape = match cell.underline_style.unwrap_or_default() {
+        UnderlineStyle::Single => None,
+        UnderlineStyle::Double => Some("double"),
+        UnderlineStyle::Curly => Some("wavy"),
+        UnderlineStyle::Dotted => Some("dotted"),
+        UnderlineStyle::Dashed => Some("dashed"),
+    };
+    if let Some(shape) = shape {
+        details.push(("text-decoration-style", shape.to_string()));
+    }
+    if let Some(color) = cell.underline_color {
+        details.push(("text-decoration-color", color.to_hex()));
+    }
+    details
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn same_style(a: &Cell, b: &Cell) -> bool {
+    a.style() == b.style() && a.link == b.link
+}
+
+/// Splits every row into runs of equally styled cells, dropping unstyled blanks at the end of the row
+fn runs(buffer: &Buffer) -> Vec<Vec<(u16, Vec<&Cell>)>> {
+    (0..buffer.height())
+        .map(|y| {
+            let row = (0..buffer.width()).filter_map(|x| buffer.get(x, y)).collect::<Vec<_>>();
+            let end = row.iter().rposition(|c| c.symbol != " " || c.is_styled()).map_or(0, |i| i + 1);
+
+            let mut runs: Vec<(u16, Vec<&Cell>)> = Vec::new();
+            for (x, cell) in row[..end].iter().enumerate() {
+                match runs.last_mut() {
+                    Some((_, run)) if same_style(run[0], cell) => run.push(cell),
+                    _ => runs.push((x as u16, vec![cell])),
+                }
+            }
+            runs
+        })
+        .collect()
+}
+
+/// Lays a `Text` out on a buffer just big enough to hold it
+fn text_buffer(text: &Text) -> Buffer {
+    let height = text.render_plain().lines().count().max(1);
+    Buffer::from_ansi(&text.render(), text.width() as u16, height as u16)
+}
+
+impl Buffer {
+
+    /// * Exports the frame as a standalone HTML document, with inline styles for every run of equally styled cells
+    pub fn to_html(&self) -> String {
+        let mut body = String::new();
+
+        for (y, row) in runs(self).into_iter().enumerate() {
+            if y > 0 {
+                body.push('\n');
+            }
+            for (_, run) in row {
+                let cell = run[0];
+                let mut content = escape(&run.iter().map(|c| c.symbol.as_str()).collect::<String>());
+                if let Some(url) = &cell.link {
+                    content = format!("<a href=\"{}\">{}</a>", escape(url), content);
+                }
+                if !cell.is_styled() {
+                    body.push_str(&content);
+                    continue;
+                }
+
+                let (fg, bg) = painted_colors(cell);
+                let mut css = vec![format!("color:{}", fg.to_hex())];
+                if bg != DEFAULT_BG {
+                    css.push(format!("background-color:{}", bg.to_hex()));
+                }
+                if cell.modifiers.contains(Modifier::Bold) {
+                    css.push("font-weight:bold".to_string());
+                }
+                if cell.modifiers.contains(Modifier::Italic) {
+                    css.push("font-style:italic".to_string());
+                }
+                if cell.modifiers.contains(Modifier::Dim) {
+                    css.push("opacity:0.5".to_string());
+                }
+                if let Some(decoration) = text_decoration(cell) {
+                    css.push(format!("text-decoration:{}", decoration));
+                }
+                css.extend(underline_details(cell).into_iter().map(|(k, v)| format!("{}:{}", k, v)));
+                body.push_str(&format!("<span style=\"{}\">{}</span>", css.join(";"), content));
+            }
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>tuitui</title>\n</head>\n<body style=\"margin:0;background:{bg}\">\n<pre style=\"margin:0;padding:8px;font-family:{font};font-size:{size}px;line-height:{height}px;color:{fg};background:{bg}\">{body}</pre>\n</body>\n</html>\n",
+            bg = DEFAULT_BG.to_hex(),
+            fg = DEFAULT_FG.to_hex(),
+            font = escape(FONT_FAMILY),
+            size = FONT_SIZE,
+            height = CELL_HEIGHT,
+            body = body,
+        )
+    }
+
+
+    /// * Exports the frame as an SVG image, drawing every cell on a monospace grid
+    pub fn to_svg(&self) -> String {
+        let width = self.width() as f32 * CELL_WIDTH;
+        let height = self.height() as f32 * CELL_HEIGHT;
+        let mut backgrounds = String::new();
+        let mut texts = String::new();
+
+        for (y, row) in runs(self).into_iter().enumerate() {
+            let top = y as f32 * CELL_HEIGHT;
+            for (x, run) in row {
+                let cell = run[0];
+                let left = x as f32 * CELL_WIDTH;
+                let run_width = run.len() as f32 * CELL_WIDTH;
+                let (fg, bg) = painted_colors(cell);
+
+                if bg != DEFAULT_BG {
+                    backgrounds.push_str(&format!(
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+                        left, top, run_width, CELL_HEIGHT, bg.to_hex()
+                    ));
+                }
+
+                let content = run.iter().map(|c| c.symbol.as_str()).collect::<String>();
+                if content.trim().is_empty() {
+                    continue;
+                }
+
+                let mut attributes = format!("fill=\"{}\"", fg.to_hex());
+                if cell.modifiers.contains(Modifier::Bold) {
+                    attributes.push_str(" font-weight=\"bold\"");
+                }
+                if cell.modifiers.contains(Modifier::Italic) {
+                    attributes.push_str(" font-style=\"italic\"");
+                }
+                if cell.modifiers.contains(Modifier::Dim) {
+                    attributes.push_str(" opacity=\"0.5\"");
+                }
+                if let Some(decoration) = text_decoration(cell) {
+                    attributes.push_str(&format!(" text-decoration=\"{}\"", decoration));
+                }
+                let details = underline_details(cell);
+                if !details.is_empty() {
+                    let css = details.iter().map(|(k, v)| format!("{}:{}", k, v)).collect::<Vec<_>>();
+                    attributes.push_str(&format!(" style=\"{}\"", css.join(";")));
+                }
+                texts.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" textLength=\"{:.1}\" lengthAdjust=\"spacingAndGlyphs\" {}>{}</text>\n",
+                    left,
+                    top + CELL_HEIGHT * 0.75,
+                    run_width,
+                    attributes,
+                    escape(&content)
+                ));
+            }
+        }
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.1}\" height=\"{h:.1}\" viewBox=\"0 0 {w:.1} {h:.1}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{bg}\"/>\n{backgrounds}<g font-family=\"{font}\" font-size=\"{size}\" xml:space=\"preserve\">\n{texts}</g>\n</svg>\n",
+            w = width,
+            h = height,
+            bg = DEFAULT_BG.to_hex(),
+            backgrounds = backgrounds,
+            font = escape(FONT_FAMILY),
+            size = FONT_SIZE,
+            texts = texts,
+        )
+    }
+}
+
+impl Ui {
+
+    /// * Renders the `Ui` on a screen of the given size and exports it as HTML
+    ///
+    /// Like snapshots, exports show the mask of a password input even while its value is revealed on screen.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let mut ui = Ui::new();
+    /// ui.text(text!(span!("error", color Color::Red, bold)));
+    ///
+    /// let html = ui.to_html(20, 2);
+    /// assert!(html.contains("<span style=\"color:#cd0000;font-weight:bold\">error</span>"));
+    ///
+    /// let mut password = PasswordInput::new().with_width(8);
+    /// for c in "hunter2".chars() {
+    ///     password.handle_key(Key::from_char(c).unwrap());
+    /// }
+    /// password.toggle_reveal();
+    /// let mut ui = Ui::new();
+    /// ui.password_input(&password);
+    ///
+    /// assert!(ui.render().contains("hunter2"));
+    /// assert!(!ui.to_html(20, 2).contains("hunter2"));
+    /// assert!(ui.to_html(20, 2).contains("•••••••"));
+    /// ```
+    pub fn to_html(&self, width: u16, height: u16) -> String {
+        self.to_public_buffer(width, height).to_html()
+    }
+
+
+    /// Like `to_html`, but as SVG
+    pub fn to_svg(&self, width: u16, height: u16) -> String {
+        self.to_public_buffer(width, height).to_svg()
+    }
+}
+
+impl Text {
+
+    /// Exports the text as HTML, sized to fit its longest line
+    pub fn to_html(&self) -> String {
+        text_buffer(self).to_html()
+    }
+
+
+    /// Exports the text as SVG, sized to fit its longest line
+    pub fn to_svg(&self) -> String {
+        text_buffer(self).to_svg()
+    }
+}
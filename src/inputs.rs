@@ -2,7 +2,10 @@ use strum_macros::{EnumIter, AsRefStr};
 use convert_case::{Case, Casing};
 use crossterm::event::{self, KeyEventKind};
 use std::time::Duration;
-use event::{KeyCode, KeyEvent, KeyModifiers};
+use event::{KeyCode, KeyEvent};
+use crate::event::Event;
+
+pub use crossterm::event::KeyModifiers;
 
 const TYPEABLE_KEYS: &[InputKey] = &[
     InputKey::Q, InputKey::W, InputKey::E, InputKey::R, InputKey::T, InputKey::Y, InputKey::U, InputKey::I, InputKey::O, InputKey::P, 
@@ -83,6 +86,14 @@ impl InputKey {
     pub fn is_action_key(&self) -> bool {
         ACTION_KEYS.contains(self)
     }
+
+    
+    /// The character typed by this key without Shift, if it types one
+    pub fn to_char(&self) -> Option<char> {
+        (' '..='~')
+            .filter(|c| !c.is_ascii_uppercase())
+            .find(|c| Key::from_char(*c).is_some_and(|k| k.key == *self))
+    }
 }
 
 impl TryFrom<KeyEvent> for InputKey {
@@ -168,7 +179,7 @@ impl TryFrom<KeyEvent> for InputKey {
                 '7' => Ok(InputKey::Num7),
                 '8' => Ok(InputKey::Num8),
                 '9' => Ok(InputKey::Num9),
-                _ => unreachable!()
+                _ => Err(KeyConversionError { details: format!("Key not implemented: {}", code) })
             }
 
             KeyCode::Backspace => Ok(InputKey::Backspace),
@@ -216,6 +227,33 @@ pub struct Key {
 
 impl Key {
     
+    pub fn new(key: InputKey) -> Self {
+        Self {
+            key,
+            modifiers: KeyModifiers::NONE,
+            character: None,
+        }
+    }
+    
+    
+    /// Builds the key that typing `c` would produce, if `c` maps to an `InputKey`
+    pub fn from_char(c: char) -> Option<Self> {
+        let key = InputKey::try_from(KeyEvent::from(KeyCode::Char(c))).ok()?;
+        let modifiers = if c.is_uppercase() { KeyModifiers::SHIFT } else { KeyModifiers::NONE };
+        Some(Self {
+            key,
+            modifiers,
+            character: Some(c),
+        })
+    }
+    
+    
+    pub fn with_modifiers(mut self, modifiers: KeyModifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+    
+    
     pub fn ctrl(&self) -> bool {
         self.modifiers.contains(KeyModifiers::CONTROL)
     }
@@ -231,6 +269,53 @@ impl Key {
     }
 
     
+    /// Encodes the key as the bytes a terminal would send for it, the inverse of `InputDecoder`
+    pub fn to_ansi(&self) -> String {
+        if let Some(c) = self.character.or_else(|| self.key.to_char()) {
+            if self.ctrl() && c.is_ascii_alphabetic() {
+                let control = (c.to_ascii_lowercase() as u8 - b'a' + 1) as char;
+                return if self.alt() { format!("\x1b{}", control) } else { control.to_string() };
+            }
+            return if self.alt() { format!("\x1b{}", c) } else { c.to_string() };
+        }
+
+        let modifier = 1 + u8::from(self.shift()) + 2 * u8::from(self.alt()) + 4 * u8::from(self.ctrl());
+        let csi = |suffix: char| if modifier > 1 { format!("\x1b[1;{}{}", modifier, suffix) } else { format!("\x1b[{}", suffix) };
+        let tilde = |number: u8| if modifier > 1 { format!("\x1b[{};{}~", number, modifier) } else { format!("\x1b[{}~", number) };
+
+        match self.key {
+            InputKey::Enter => "\r".to_string(),
+            InputKey::Tab => "\t".to_string(),
+            InputKey::Backspace => "\x7f".to_string(),
+            InputKey::Esc => "\x1b".to_string(),
+            InputKey::UpArrow => csi('A'),
+            InputKey::DownArrow => csi('B'),
+            InputKey::RightArrow => csi('C'),
+            InputKey::LeftArrow => csi('D'),
+            InputKey::Home => csi('H'),
+            InputKey::End => csi('F'),
+            InputKey::BackTab => "\x1b[Z".to_string(),
+            InputKey::F1 => "\x1bOP".to_string(),
+            InputKey::F2 => "\x1bOQ".to_string(),
+            InputKey::F3 => "\x1bOR".to_string(),
+            InputKey::F4 => "\x1bOS".to_string(),
+            InputKey::Insert => tilde(2),
+            InputKey::Delete => tilde(3),
+            InputKey::PageUp => tilde(5),
+            InputKey::PageDown => tilde(6),
+            InputKey::F5 => tilde(15),
+            InputKey::F6 => tilde(17),
+            InputKey::F7 => tilde(18),
+            InputKey::F8 => tilde(19),
+            InputKey::F9 => tilde(20),
+            InputKey::F10 => tilde(21),
+            InputKey::F11 => tilde(23),
+            InputKey::F12 => tilde(24),
+            _ => String::new(),
+        }
+    }
+
+    
     pub fn to_friendly_string(&self) -> String {
         let mut output = String::new();
         output.push_str(&format!("{} {}", self.key.to_friendly_string(), if let Some(ch) = self.character { format!("( {} )", ch)} else { "".to_string() }));
@@ -238,6 +323,7 @@ impl Key {
     }
 }
 
+#[derive(Default)]
 pub struct InputHandler;
 
 impl InputHandler {
@@ -247,32 +333,229 @@ impl InputHandler {
     
     
     pub fn poll(&mut self, timeout: Duration) -> Option<Key> {
-        if event::poll(timeout).ok()? {
-            if let Ok(crossterm_event) = event::read() {
-                if let event::Event::Key(key_event) = crossterm_event {
-                    if !(key_event.kind == KeyEventKind::Press) {
-                        return None
+        match self.poll_event(timeout)? {
+            Event::Key(key) => Some(key),
+            _ => None,
+        }
+    }
+    
+    
+    /// Like `poll`, but also reports terminal resizes
+    pub fn poll_event(&mut self, timeout: Duration) -> Option<Event> {
+        if !event::poll(timeout).ok()? {
+            return None
+        }
+        match event::read().ok()? {
+            event::Event::Key(key_event) => {
+                if key_event.kind != KeyEventKind::Press {
+                    return None
+                }
+                match InputKey::try_from(key_event) {
+                    Ok(input_key) => {
+                        let character = match key_event.code {
+                            KeyCode::Char(c) => Some(c),
+                            _ => None,
+                        };
+                        
+                        Some(Event::Key(Key {
+                            key: input_key,
+                            modifiers: key_event.modifiers,
+                            character,
+                        }))
                     }
-                    match InputKey::try_from(key_event) {
-                        Ok(input_key) => {
-                            let character = match key_event.code {
-                                KeyCode::Char(c) => Some(c),
-                                _ => None,
-                            };
-                            
-                            return Some(Key {
-                                key: input_key,
-                                modifiers: key_event.modifiers,
-                                character,
-                            });
-                        }
-                        Err(e) => {
-                            eprintln!("Unsupported key: {}", e.details);
-                        }
+                    Err(e) => {
+                        eprintln!("Unsupported key: {}", e.details);
+                        None
                     }
                 }
             }
+            event::Event::Resize(width, height) => Some(Event::Resize(width, height)),
+            _ => None,
+        }
+    }
+}
+
+
+/// * Decodes the raw bytes a terminal sends (characters and VT escape sequences) into `Key`s
+///
+/// Used by backends that read input from a plain byte stream instead of through crossterm.
+/// Incomplete escape sequences are kept until the next call to `feed`.
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut decoder = InputDecoder::new();
+/// let keys = decoder.feed(b"a\x1b[A\r");
+/// assert_eq!(keys.iter().map(|k| k.key).collect::<Vec<_>>(), [InputKey::A, InputKey::UpArrow, InputKey::Enter]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InputDecoder {
+    pending: Vec<u8>,
+}
+
+impl InputDecoder {
+    
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Key> {
+        only_keys(self.feed_events(bytes))
+    }
+    
+    
+    /// Like `feed`, but also reports window size reports (`ESC [ 8 ; rows ; columns t`) as `Event::Resize`
+    pub fn feed_events(&mut self, bytes: &[u8]) -> Vec<Event> {
+        self.pending.extend_from_slice(bytes);
+        self.decode_pending(true)
+    }
+    
+    
+    /// Decodes whatever is still pending, treating a lone `Esc` as the Esc key
+    pub fn flush(&mut self) -> Vec<Key> {
+        only_keys(self.flush_events())
+    }
+    
+    
+    pub fn flush_events(&mut self) -> Vec<Event> {
+        let events = self.decode_pending(false);
+        self.pending.clear();
+        events
+    }
+    
+    
+    fn decode_pending(&mut self, wait_for_more: bool) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut i = 0;
+
+        while i < self.pending.len() {
+            match decode_key(&self.pending[i..], wait_for_more) {
+                Decoded::Event(event, used) => {
+                    events.extend(event);
+                    i += used;
+                }
+                Decoded::Incomplete => break,
+            }
         }
-        None
+
+        self.pending.drain(..i);
+        events
     }
-}
\ No newline at end of file
+}
+
+fn only_keys(events: Vec<Event>) -> Vec<Key> {
+    events
+        .into_iter()
+        .filter_map(|e| match e {
+            Event::Key(key) => Some(key),
+            _ => None,
+        })
+        .collect()
+}
+
+enum Decoded {
+    Event(Option<Event>, usize),
+    Incomplete,
+}
+
+fn decode_key(bytes: &[u8], wait_for_more: bool) -> Decoded {
+    let plain = |key: InputKey, used: usize| Decoded::Event(Some(Event::Key(Key::new(key))), used);
+
+    match bytes[0] {
+        b'\r' | b'\n' => plain(InputKey::Enter, 1),
+        b'\t' => plain(InputKey::Tab, 1),
+        0x7f | 0x08 => plain(InputKey::Backspace, 1),
+        0x1b => match bytes.get(1) {
+            None if wait_for_more => Decoded::Incomplete,
+            None => plain(InputKey::Esc, 1),
+            Some(b'[') | Some(b'O') => decode_escape_sequence(bytes, wait_for_more),
+            Some(_) => match decode_key(&bytes[1..], wait_for_more) {
+                Decoded::Event(Some(Event::Key(k)), used) => {
+                    Decoded::Event(Some(Event::Key(k.with_modifiers(k.modifiers | KeyModifiers::ALT))), used + 1)
+                }
+                Decoded::Event(event, used) => Decoded::Event(event, used + 1),
+                Decoded::Incomplete => Decoded::Incomplete,
+            },
+        },
+        b @ 0x01..=0x1a => {
+            let c = (b - 1 + b'a') as char;
+            let key = Key::from_char(c).map(|k| k.with_modifiers(KeyModifiers::CONTROL));
+            Decoded::Event(key.map(Event::Key), 1)
+        }
+        b => {
+            let len = match b {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            if bytes.len() < len {
+                return if wait_for_more { Decoded::Incomplete } else { Decoded::Event(None, bytes.len()) };
+            }
+            let key = std::str::from_utf8(&bytes[..len])
+                .ok()
+                .and_then(|s| s.chars().next())
+                .and_then(Key::from_char);
+            Decoded::Event(key.map(Event::Key), len)
+        }
+    }
+}
+
+fn decode_escape_sequence(bytes: &[u8], wait_for_more: bool) -> Decoded {
+    let end = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b));
+    let Some(end) = end.map(|e| e + 2) else {
+        return if wait_for_more { Decoded::Incomplete } else { Decoded::Event(None, bytes.len()) };
+    };
+
+    let params = std::str::from_utf8(&bytes[2..end])
+        .unwrap_or("")
+        .split(';')
+        .map(|p| p.parse::<u16>().ok())
+        .collect::<Vec<_>>();
+    let number = params.first().copied().flatten();
+
+    if let (b't', Some(8), Some(Some(rows)), Some(Some(columns))) = (bytes[end], number, params.get(1), params.get(2)) {
+        return Decoded::Event(Some(Event::Resize(*columns, *rows)), end + 1);
+    }
+
+    let modifiers = match params.get(1).copied().flatten().map(|m| m.saturating_sub(1)) {
+        Some(m) => {
+            let mut modifiers = KeyModifiers::NONE;
+            if m & 1 != 0 { modifiers |= KeyModifiers::SHIFT }
+            if m & 2 != 0 { modifiers |= KeyModifiers::ALT }
+            if m & 4 != 0 { modifiers |= KeyModifiers::CONTROL }
+            modifiers
+        }
+        None => KeyModifiers::NONE,
+    };
+
+    let key = match (bytes[end], number) {
+        (b'A', _) => Some(InputKey::UpArrow),
+        (b'B', _) => Some(InputKey::DownArrow),
+        (b'C', _) => Some(InputKey::RightArrow),
+        (b'D', _) => Some(InputKey::LeftArrow),
+        (b'H', _) => Some(InputKey::Home),
+        (b'F', _) => Some(InputKey::End),
+        (b'Z', _) => Some(InputKey::BackTab),
+        (b'P', _) => Some(InputKey::F1),
+        (b'Q', _) => Some(InputKey::F2),
+        (b'R', _) => Some(InputKey::F3),
+        (b'S', _) => Some(InputKey::F4),
+        (b'~', Some(1)) | (b'~', Some(7)) => Some(InputKey::Home),
+        (b'~', Some(2)) => Some(InputKey::Insert),
+        (b'~', Some(3)) => Some(InputKey::Delete),
+        (b'~', Some(4)) | (b'~', Some(8)) => Some(InputKey::End),
+        (b'~', Some(5)) => Some(InputKey::PageUp),
+        (b'~', Some(6)) => Some(InputKey::PageDown),
+        (b'~', Some(15)) => Some(InputKey::F5),
+        (b'~', Some(17)) => Some(InputKey::F6),
+        (b'~', Some(18)) => Some(InputKey::F7),
+        (b'~', Some(19)) => Some(InputKey::F8),
+        (b'~', Some(20)) => Some(InputKey::F9),
+        (b'~', Some(21)) => Some(InputKey::F10),
+        (b'~', Some(23)) => Some(InputKey::F11),
+        (b'~', Some(24)) => Some(InputKey::F12),
+        _ => None,
+    };
+
+    Decoded::Event(key.map(|k| Event::Key(Key::new(k).with_modifiers(modifiers))), end + 1)
+}
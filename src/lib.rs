@@ -18,10 +18,10 @@
 //! 
 //! impl App for MyApp {
 //!     fn display(&mut self, ui: &mut Ui) {
-//!         ui.heading("Hello tuitui! 🐧");
+//!         ui.heading("Hello tuitui! 🐧".into());
 //!     }
 //! 
-//!     fn update(&mut self) -> bool {
+//!     fn update(&mut self, _key: Option<Key>) -> bool {
 //!         false    
 //!     }
 //! 
@@ -30,8 +30,8 @@
 //!     }
 //! }
 //! 
-//! fn main() {
-//!     AppRuntime::new().run(MyApp);
+//! fn main() -> Result<()> {
+//!     AppRuntime::new().run(MyApp)
 //! }
 //! ```
 
@@ -40,8 +40,25 @@ pub(crate) mod inputs;
 pub(crate) mod event;
 pub(crate) mod app;
 pub(crate) mod ui;
+pub(crate) mod ansi;
+pub(crate) mod color_depth;
+pub(crate) mod theme;
+#[cfg(feature = "theme-files")]
+pub(crate) mod theme_file;
+pub(crate) mod buffer;
+pub(crate) mod backend;
+#[cfg(feature = "record")]
+pub(crate) mod record;
+pub(crate) mod export;
+#[cfg(feature = "server")]
+pub(crate) mod server;
 pub mod components;
 pub mod macros;
+pub mod markup;
+pub mod width;
+pub mod testing;
+#[cfg(feature = "regex")]
+pub use regex;
 
 pub mod prelude {
     #![allow(unused_imports)]
@@ -50,8 +67,20 @@ pub mod prelude {
     pub use crate::app::*;
     pub use std::time::Duration;
     pub use crate::ui::*;
+    pub use crate::buffer::*;
+    pub use crate::color_depth::ColorDepth;
+    pub use crate::theme::Theme;
+    #[cfg(feature = "theme-files")]
+    pub use crate::theme_file::ThemeError;
+    pub use crate::backend::*;
+    #[cfg(feature = "record")]
+    pub use crate::record::*;
+    pub use crate::testing::*;
+    #[cfg(feature = "server")]
+    pub use crate::server::*;
     pub use crate::components::*;
-    pub use crate::{span, text};
+    pub use crate::markup::MarkupError;
+    pub use crate::{span, text, markup, assert_frame_snapshot};
     pub use text::*;
     pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 }
\ No newline at end of file
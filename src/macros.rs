@@ -28,4 +28,76 @@ macro_rules! text {
             text
         }
     };
-}
\ No newline at end of file
+}
+
+/// * Builds a `Text` from markup, checking the markup at compile time
+///
+/// Extra arguments are formatted into `{}` placeholders and escaped, so they always show up literally.
+/// ```
+/// # use tuitui::prelude::*;
+/// let file = "[main].rs";
+/// let text = markup!("[bold red]error[/]: {} not found", file);
+/// assert_eq!(text.render_plain(), "error: [main].rs not found");
+/// ```
+/// Invalid markup fails to compile:
+/// ```compile_fail
+/// # use tuitui::prelude::*;
+/// let text = markup!("[bold redd]error[/]");
+/// ```
+#[macro_export]
+macro_rules! markup {
+    ($markup:literal $(,)?) => {
+        {
+            const _: () = $crate::markup::validate($markup);
+            $crate::components::text::Text::markup($markup).expect("markup was checked at compile time")
+        }
+    };
+
+    ($markup:literal, $($arg:expr),+ $(,)?) => {
+        {
+            const _: () = $crate::markup::validate($markup);
+            $crate::components::text::Text::markup(&format!($markup, $($crate::markup::escape(&$arg.to_string())),+))
+                .expect("markup was checked at compile time")
+        }
+    };
+}
+
+/// * Renders a frame and compares it against a golden file in `tests/snapshots`
+///
+/// Works with a `Ui` (rendered at 80x24 unless a size is given), a `Buffer` or a `TestRuntime`.
+/// Snapshots are named after the calling function unless a name is given.
+/// Run the tests with `TUITUI_UPDATE_SNAPSHOTS=1` to create or update the golden files.
+/// ```no_run
+/// # use tuitui::prelude::*;
+/// let mut ui = Ui::new();
+/// ui.heading("Settings".into());
+///
+/// assert_frame_snapshot!(ui, 40, 5);
+/// assert_frame_snapshot!(name: "settings_small", ui, 10, 2);
+/// ```
+#[macro_export]
+macro_rules! assert_frame_snapshot {
+    (name: $name:expr, $frame:expr, $width:expr, $height:expr $(,)?) => {
+        {
+            use $crate::testing::ToFrame as _;
+            let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("snapshots");
+            $crate::testing::assert_snapshot(&dir, $name, &$frame.to_frame($width, $height));
+        }
+    };
+
+    (name: $name:expr, $frame:expr $(,)?) => {
+        $crate::assert_frame_snapshot!(name: $name, $frame, 80, 24)
+    };
+
+    ($frame:expr, $width:expr, $height:expr $(,)?) => {
+        {
+            fn f() {}
+            let name = $crate::testing::next_snapshot_name(::std::any::type_name_of_val(&f));
+            $crate::assert_frame_snapshot!(name: &name, $frame, $width, $height)
+        }
+    };
+
+    ($frame:expr $(,)?) => {
+        $crate::assert_frame_snapshot!($frame, 80, 24)
+    };
+}
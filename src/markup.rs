@@ -0,0 +1,483 @@
+//! # Markup
+//!
+//! A short way of writing styled `Text`:
+//!
+//! ```
+//! # use tuitui::prelude::*;
+//! let text = Text::markup("[bold red]error[/]: file [italic #7a62a8]main.rs[/] not found").unwrap();
+//! assert_eq!(text.render_plain(), "error: file main.rs not found");
+//! ```
+//!
+//! - `[tags]` opens a style made of space separated tags, until the matching `[/]` or `[/tags]`.
+//!   Styles nest, and anything still open at the end is closed automatically
+//! - Styles: `bold`/`b`, `dim`/`d`, `italic`/`i`, `underline`/`u`, `blink`, `reverse`, `conceal`/`hidden` and `strikethrough`/`strike`/`s`
+//! - Colors: every `Color` by name (`red`, `bright_red`, `light_blue`, ...), `#rrggbb`, `#rgb`, `rgb(r, g, b)`
+//!   and `color(n)` for an entry of the 256 color palette
+//! - `on <color>` sets the background color
+//! - `\[` is a literal `[` and `\\` a literal `\`
+//!
+//! The `markup!` macro checks its markup at compile time.
+
+use std::fmt;
+use crate::components::{text::{Color, Text}, Modifier};
+
+/// * Describes what is wrong with a piece of markup and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    pub details: String,
+    /// Byte offset of the problem in the markup
+    pub position: usize,
+}
+
+impl fmt::Display for MarkupError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.details, self.position)
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+
+/// The style opened by one tag
+#[derive(Debug, Clone, Copy)]
+struct TagStyle {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    styles: [bool; 8],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    /// Plain text between `start` and `end`
+    Text(usize, usize),
+    /// A `\` escaped character starting at the given byte
+    Escaped(usize),
+    Open(TagStyle, usize, usize),
+    /// A closing tag with the optional name between `start` and `end`
+    Close(usize, usize),
+}
+
+type ParseResult<T> = Result<T, (&'static str, usize)>;
+
+const fn slice(bytes: &[u8], start: usize, end: usize) -> &[u8] {
+    let (head, _) = bytes.split_at(end);
+    let (_, middle) = head.split_at(start);
+    middle
+}
+
+/// Compares a word to a lowercase name, ignoring ASCII case and treating `-` like `_`
+const fn word_is(word: &[u8], name: &str) -> bool {
+    let name = name.as_bytes();
+    if word.len() != name.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < word.len() {
+        let c = match word[i].to_ascii_lowercase() {
+            b'-' => b'_',
+            c => c,
+        };
+        if c != name[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+pub(crate) const fn parse_hex_color(digits: &[u8]) -> Option<Color> {
+    if digits.len() != 3 && digits.len() != 6 {
+        return None;
+    }
+    let mut values = [0u8; 6];
+    let mut i = 0;
+    while i < digits.len() {
+        values[i] = match hex_digit(digits[i]) {
+            Some(v) => v,
+            None => return None,
+        };
+        i += 1;
+    }
+    match digits.len() {
+        3 => Some(Color::RGB(values[0] * 17, values[1] * 17, values[2] * 17)),
+        6 => Some(Color::RGB(values[0] * 16 + values[1], values[2] * 16 + values[3], values[4] * 16 + values[5])),
+        _ => None,
+    }
+}
+
+/// Parses the inside of `rgb(...)`
+const fn parse_rgb_color(args: &[u8]) -> Option<Color> {
+    let mut values = [0u16; 3];
+    let mut count = 0;
+    let mut digits = 0;
+    let mut i = 0;
+    while i <= args.len() {
+        let c = if i < args.len() { args[i] } else { b',' };
+        match c {
+            b'0'..=b'9' => {
+                if count >= 3 {
+                    return None;
+                }
+                values[count] = values[count] * 10 + (c - b'0') as u16;
+                digits += 1;
+                if values[count] > 255 {
+                    return None;
+                }
+            }
+            b',' => {
+                if digits == 0 {
+                    return None;
+                }
+                count += 1;
+                digits = 0;
+            }
+            b' ' => {}
+            _ => return None,
+        }
+        i += 1;
+    }
+    if count != 3 {
+        return None;
+    }
+    Some(Color::RGB(values[0] as u8, values[1] as u8, values[2] as u8))
+}
+
+/// Parses the inside of `color(...)`, an index of the 256 color palette
+const fn parse_indexed_color(digits: &[u8]) -> Option<Color> {
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        if !digits[i].is_ascii_digit() {
+            return None;
+        }
+        value = value * 10 + (digits[i] - b'0') as u16;
+        if value > 255 {
+            return None;
+        }
+        i += 1;
+    }
+    Some(Color::Indexed(value as u8))
+}
+
+pub(crate) const fn parse_color(word: &[u8]) -> Option<Color> {
+    if !word.is_empty() && word[0] == b'#' {
+        let (_, digits) = word.split_at(1);
+        return parse_hex_color(digits);
+    }
+    if word.len() > 5 && word_is(slice(word, 0, 4), "rgb(") && word[word.len() - 1] == b')' {
+        return parse_rgb_color(slice(word, 4, word.len() - 1));
+    }
+    if word.len() > 7 && word_is(slice(word, 0, 6), "color(") && word[word.len() - 1] == b')' {
+        return parse_indexed_color(slice(word, 6, word.len() - 1));
+    }
+
+    let names: [(&str, Color); 18] = [
+        ("black", Color::Black),
+        ("red", Color::Red),
+        ("green", Color::Green),
+        ("yellow", Color::Yellow),
+        ("blue", Color::Blue),
+        ("magenta", Color::Magenta),
+        ("cyan", Color::Cyan),
+        ("white", Color::White),
+        ("gray", Color::Gray),
+        ("grey", Color::Gray),
+        ("bright_red", Color::BrightRed),
+        ("lime", Color::Lime),
+        ("banana", Color::Banana),
+        ("light_blue", Color::LightBlue),
+        ("pink", Color::Pink),
+        ("light_cyan", Color::LightCyan),
+        ("bright_white", Color::BrightWhite),
+        ("purple", Color::Magenta),
+    ];
+    let mut i = 0;
+    while i < names.len() {
+        if word_is(word, names[i].0) {
+            return Some(names[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Looks a modifier up by any of the names markup tags accept, like `bold` or `u`
+#[cfg_attr(not(feature = "theme-files"), allow(dead_code))]
+pub(crate) fn modifier_named(name: &str) -> Option<Modifier> {
+    parse_style(name.trim().to_lowercase().as_bytes()).map(|i| Modifier::ALL[i])
+}
+
+/// Returns the index of the modifier in `Modifier::ALL`
+const fn parse_style(word: &[u8]) -> Option<usize> {
+    let names: [(&str, usize); 19] = [
+        ("bold", 0), ("b", 0),
+        ("dim", 1), ("d", 1),
+        ("italic", 2), ("i", 2),
+        ("underline", 3), ("underlined", 3), ("u", 3),
+        ("blink", 4),
+        ("reverse", 5), ("reverse_video", 5), ("r", 5),
+        ("conceal", 6), ("concealed", 6), ("hidden", 6),
+        ("strikethrough", 7), ("strike", 7), ("s", 7),
+    ];
+    let mut i = 0;
+    while i < names.len() {
+        if word_is(word, names[i].0) {
+            return Some(names[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Parses the inside of an opening tag; `offset` is where it starts in the markup, for errors
+const fn parse_tag(tag: &[u8], offset: usize) -> ParseResult<TagStyle> {
+    let mut style = TagStyle {
+        fg: None,
+        bg: None,
+        styles: [false; 8],
+    };
+    let mut background = false;
+    let mut i = 0;
+    let mut words = 0;
+
+    while i < tag.len() {
+        if tag[i] == b' ' {
+            i += 1;
+            continue;
+        }
+
+        // A word runs until the next space, unless it's inside parentheses
+        let start = i;
+        let mut depth = 0;
+        while i < tag.len() && (tag[i] != b' ' || depth > 0) {
+            match tag[i] {
+                b'(' => depth += 1,
+                b')' if depth > 0 => depth -= 1,
+                _ => {}
+            }
+            i += 1;
+        }
+        if depth > 0 {
+            return Err(("unclosed parenthesis in tag", offset + start));
+        }
+        let word = slice(tag, start, i);
+        words += 1;
+
+        if background {
+            match parse_color(word) {
+                Some(color) => style.bg = Some(color),
+                None => return Err(("expected a background color after `on`", offset + start)),
+            }
+            background = false;
+        } else if word_is(word, "on") {
+            background = true;
+        } else if let Some(color) = parse_color(word) {
+            style.fg = Some(color);
+        } else if let Some(index) = parse_style(word) {
+            style.styles[index] = true;
+        } else if !word.is_empty() && word[0] == b'#' {
+            return Err(("invalid hex color, expected #rgb or #rrggbb", offset + start));
+        } else if word.len() >= 4 && word_is(slice(word, 0, 4), "rgb(") {
+            return Err(("invalid rgb color, expected rgb(r, g, b) with values up to 255", offset + start));
+        } else if word.len() >= 6 && word_is(slice(word, 0, 6), "color(") {
+            return Err(("invalid indexed color, expected color(n) with n up to 255", offset + start));
+        } else {
+            return Err(("unknown style or color", offset + start));
+        }
+    }
+
+    if background {
+        return Err(("expected a background color after `on`", offset + tag.len()));
+    }
+    if words == 0 {
+        return Err(("empty tag", offset));
+    }
+    Ok(style)
+}
+
+/// Reads the token starting at `position`, returning it and where the next one starts
+const fn next_token(bytes: &[u8], position: usize) -> ParseResult<(Token, usize)> {
+    match bytes[position] {
+        b'\\' => {
+            if position + 1 >= bytes.len() {
+                return Err(("trailing `\\`, use `\\\\` for a literal backslash", position));
+            }
+            let mut end = position + 2;
+            // Skip the rest of a multi-byte character
+            while end < bytes.len() && (bytes[end] & 0xc0) == 0x80 {
+                end += 1;
+            }
+            Ok((Token::Escaped(position + 1), end))
+        }
+        b'[' => {
+            let mut end = position + 1;
+            while end < bytes.len() && bytes[end] != b']' {
+                if bytes[end] == b'[' {
+                    return Err(("`[` inside a tag, use `\\[` for a literal bracket", end));
+                }
+                end += 1;
+            }
+            if end >= bytes.len() {
+                return Err(("unclosed tag, use `\\[` for a literal bracket", position));
+            }
+
+            if bytes[position + 1] == b'/' {
+                return Ok((Token::Close(position + 2, end), end + 1));
+            }
+            match parse_tag(slice(bytes, position + 1, end), position + 1) {
+                Ok(style) => Ok((Token::Open(style, position + 1, end), end + 1)),
+                Err(e) => Err(e),
+            }
+        }
+        _ => {
+            let mut end = position;
+            while end < bytes.len() && bytes[end] != b'\\' && bytes[end] != b'[' {
+                end += 1;
+            }
+            Ok((Token::Text(position, end), end))
+        }
+    }
+}
+
+/// Checks a whole piece of markup without building any `Text`
+const fn check(markup: &str) -> ParseResult<()> {
+    let bytes = markup.as_bytes();
+    // Where the names of the open tags are, so closing tags can be matched
+    let mut open = [(0usize, 0usize); 64];
+    let mut depth = 0;
+    let mut position = 0;
+
+    while position < bytes.len() {
+        let (token, next) = match next_token(bytes, position) {
+            Ok(t) => t,
+            Err(e) => return Err(e),
+        };
+        match token {
+            Token::Open(_, start, end) => {
+                if depth < open.len() {
+                    open[depth] = (start, end);
+                }
+                depth += 1;
+            }
+            Token::Close(start, end) => {
+                if depth == 0 {
+                    return Err(("closing tag without an open tag", position));
+                }
+                depth -= 1;
+                if start != end && depth < open.len() {
+                    let (open_start, open_end) = open[depth];
+                    if !bytes_eq(slice(bytes, start, end), slice(bytes, open_start, open_end)) {
+                        return Err(("closing tag doesn't match the last open tag", position));
+                    }
+                }
+            }
+            Token::Text(_, _) | Token::Escaped(_) => {}
+        }
+        position = next;
+    }
+    Ok(())
+}
+
+/// Panics (at compile time, when called in a constant) if the markup is invalid. Used by `markup!`
+#[doc(hidden)]
+pub const fn validate(markup: &str) {
+    if let Err((details, _)) = check(markup) {
+        panic!("{}", details);
+    }
+}
+
+/// * Escapes `[` and `\` so a string shows up literally in markup
+///
+/// ```
+/// # use tuitui::prelude::*;
+/// let name = "[not a tag]";
+/// let text = Text::markup(&format!("[b]{}[/]", tuitui::markup::escape(name))).unwrap();
+/// assert_eq!(text.render_plain(), "[not a tag]");
+/// ```
+pub fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('[', "\\[")
+}
+
+impl Text {
+
+    /// * Builds a `Text` from markup like `"[bold red on black]Hi[/] there"`, see the `markup` module
+    pub fn markup(markup: &str) -> Result<Text, MarkupError> {
+        check(markup).map_err(|(details, position)| MarkupError {
+            details: details.to_string(),
+            position,
+        })?;
+
+        let bytes = markup.as_bytes();
+        let mut stack: Vec<TagStyle> = Vec::new();
+        let mut text = Text::new();
+        let mut content = String::new();
+        let mut position = 0;
+
+        while position < bytes.len() {
+            let (token, next) = next_token(bytes, position).expect("markup was already checked");
+            match token {
+                Token::Text(start, end) => content.push_str(&markup[start..end]),
+                Token::Escaped(start) => content.push_str(&markup[start..next]),
+                Token::Open(style, _, _) => {
+                    push_span(&mut text, &mut content, &stack);
+                    stack.push(style);
+                }
+                Token::Close(_, _) => {
+                    push_span(&mut text, &mut content, &stack);
+                    stack.pop();
+                }
+            }
+            position = next;
+        }
+        push_span(&mut text, &mut content, &stack);
+
+        Ok(text)
+    }
+}
+
+/// Adds the text collected so far as a span styled by every open tag
+fn push_span(text: &mut Text, content: &mut String, stack: &[TagStyle]) {
+    if content.is_empty() {
+        return;
+    }
+
+    let span = text.span(content);
+    for tag in stack {
+        if let Some(fg) = tag.fg {
+            span.color(fg);
+        }
+        if let Some(bg) = tag.bg {
+            span.bg_color(bg);
+        }
+    }
+    for (i, modifier) in Modifier::ALL.iter().enumerate() {
+        if stack.iter().any(|tag| tag.styles[i]) {
+            span.add_modifier(*modifier);
+        }
+    }
+    content.clear();
+}
@@ -0,0 +1,405 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde_json::Value;
+use crate::{backend::{write_cells, Backend}, buffer::Cell, color_depth::ColorDepth, event::Event, inputs::InputDecoder};
+
+/// * One event of an asciicast recording
+#[derive(Debug, Clone, PartialEq)]
+pub enum CastEvent {
+    /// Bytes written to the terminal
+    Output(String),
+    /// Bytes typed by the user
+    Input(String),
+    /// The terminal was resized to a width and height
+    Resize(u16, u16),
+}
+
+/// * A session recorded in the asciicast v2 format (the `.cast` files made by asciinema)
+///
+/// Record one with `AppRuntime::with_recording` and feed its input back into an `App`
+/// with `AppRuntime::replay` or `TestRuntime::replay`:
+/// ```
+/// # use tuitui::prelude::*;
+/// let cast = "{\"version\": 2, \"width\": 40, \"height\": 10}\n[0.5, \"i\", \"q\"]\n";
+/// let recording = Recording::parse(cast).unwrap();
+/// assert_eq!(recording.input_events()[0].1, Event::Key(Key::from_char('q').unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    pub width: u16,
+    pub height: u16,
+    pub events: Vec<(Duration, CastEvent)>,
+}
+
+impl Recording {
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+
+    pub fn parse(cast: &str) -> io::Result<Self> {
+        let mut lines = cast.lines().filter(|l| !l.trim().is_empty());
+        let header: Value = serde_json::from_str(lines.next().unwrap_or(""))
+            .map_err(|e| invalid(&format!("invalid asciicast header: {}", e)))?;
+
+        if header["version"].as_u64() != Some(2) {
+            return Err(invalid("only asciicast version 2 is supported"));
+        }
+        let dimension = |key: &str| {
+            header[key]
+                .as_u64()
+                .and_then(|n| u16::try_from(n).ok())
+                .ok_or_else(|| invalid(&format!("header has no {}", key)))
+        };
+        let (width, height) = (dimension("width")?, dimension("height")?);
+
+        let mut events = Vec::new();
+        for line in lines {
+            let event: Value = serde_json::from_str(line).map_err(|_| invalid(&format!("invalid event: {}", line)))?;
+            let (Some(time), Some(code), Some(data)) = (event[0].as_f64(), event[1].as_str(), event[2].as_str()) else {
+                return Err(invalid(&format!("invalid event: {}", line)));
+            };
+
+            let event = match code {
+                "o" => CastEvent::Output(data.to_string()),
+                "i" => CastEvent::Input(data.to_string()),
+                "r" => {
+                    let (w, h) = data.split_once('x').ok_or_else(|| invalid(&format!("invalid resize: {}", data)))?;
+                    let parse = |n: &str| n.trim().parse::<u16>().map_err(|_| invalid(&format!("invalid resize: {}", data)));
+                    CastEvent::Resize(parse(w)?, parse(h)?)
+                }
+                // Markers and custom events don't affect the app
+                _ => continue,
+            };
+            events.push((Duration::from_secs_f64(time.max(0.0)), event));
+        }
+
+        Ok(Self { width, height, events })
+    }
+
+
+    /// The recorded keys and resizes, decoded into `Event`s, along with when they happened
+    pub fn input_events(&self) -> Vec<(Duration, Event)> {
+        let mut decoder = InputDecoder::new();
+        let mut output = Vec::new();
+
+        for (time, event) in &self.events {
+            match event {
+                CastEvent::Input(data) => {
+                    let mut events = decoder.feed_events(data.as_bytes());
+                    events.extend(decoder.flush_events());
+                    output.extend(events.into_iter().map(|e| (*time, e)));
+                }
+                CastEvent::Resize(width, height) => output.push((*time, Event::Resize(*width, *height))),
+                CastEvent::Output(_) => {}
+            }
+        }
+        output
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// * Wraps a backend and writes everything it draws, and every input event, to an asciicast v2 file
+///
+/// Output is written as one event per flushed frame. Sensitive frames, like ones with a password input, are left out
+/// along with the keys pressed on them, so a recording made through a login can't be replayed past it.
+pub struct RecordingBackend<B: Backend, W: Write = BufWriter<File>> {
+    inner: B,
+    cast: W,
+    start: Instant,
+    pending: Vec<u8>,
+    color_depth: ColorDepth,
+    sensitive: bool,
+    /// The last key, with its time, kept until it's known whether the frame it leads to is sensitive
+    held_key: Option<(f64, String)>,
+}
+
+impl<B: Backend> RecordingBackend<B> {
+
+    pub fn create<P: AsRef<Path>>(inner: B, path: P) -> io::Result<Self> {
+        Self::new(inner, BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<B: Backend, W: Write> RecordingBackend<B, W> {
+
+    pub fn new(inner: B, mut cast: W) -> io::Result<Self> {
+        let (width, height) = inner.size()?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        writeln!(
+            cast,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}}}",
+            width, height, timestamp
+        )?;
+
+        Ok(Self {
+            inner,
+            cast,
+            start: Instant::now(),
+            pending: Vec::new(),
+            color_depth: ColorDepth::TrueColor,
+            sensitive: false,
+            held_key: None,
+        })
+    }
+
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+
+    pub fn into_inner(self) -> (B, W) {
+        (self.inner, self.cast)
+    }
+
+
+    fn write_event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        self.write_event_at(self.start.elapsed().as_secs_f64(), code, data)
+    }
+
+
+    fn write_held_key(&mut self) -> io::Result<()> {
+        match self.held_key.take() {
+            Some((time, key)) => self.write_event_at(time, "i", &key),
+            None => Ok(()),
+        }
+    }
+
+
+    fn write_event_at(&mut self, time: f64, code: &str, data: &str) -> io::Result<()> {
+        writeln!(
+            self.cast,
+            "[{:.6}, \"{}\", {}]",
+            time,
+            code,
+            Value::from(data)
+        )?;
+        self.cast.flush()
+    }
+}
+
+impl<B: Backend, W: Write> Backend for RecordingBackend<B, W> {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if self.sensitive {
+            return self.inner.draw(content);
+        }
+        let content = content.collect::<Vec<_>>();
+        write_cells(&mut self.pending, content.iter().copied(), self.color_depth)?;
+        self.inner.draw(content.into_iter())
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        write!(self.pending, "\x1b[{};{}H", y + 1, x + 1)?;
+        self.inner.move_cursor(x, y)
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.pending.extend_from_slice(b"\x1b[?25l");
+        self.inner.hide_cursor()
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.pending.extend_from_slice(b"\x1b[?25h");
+        self.inner.show_cursor()
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.pending.extend_from_slice(b"\x1b[2J\x1b[3J\x1b[1;1H");
+        self.inner.clear()
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Still held when the app quit on the key, before drawing another frame
+        self.write_held_key()?;
+        if self.sensitive {
+            self.pending.clear();
+        } else if !self.pending.is_empty() {
+            let output = String::from_utf8_lossy(&std::mem::take(&mut self.pending)).into_owned();
+            self.write_event("o", &output)?;
+        }
+        self.inner.flush()
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        self.inner.size()
+    }
+
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let event = self.inner.poll_event(timeout)?;
+        match event {
+            Some(Event::Key(_)) if self.sensitive => {}
+            Some(Event::Key(key)) => self.held_key = Some((self.start.elapsed().as_secs_f64(), key.to_ansi())),
+            Some(Event::Resize(width, height)) => self.write_event("r", &format!("{}x{}", width, height))?,
+            _ => {}
+        }
+        Ok(event)
+    }
+
+
+    fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.color_depth = depth;
+        self.inner.set_color_depth(depth)
+    }
+
+
+    /// Copied text is passed on but left out of the recording, as it may be anything
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        self.inner.set_clipboard(text)
+    }
+
+
+    /// A held key is written now, unless the frame it leads to is sensitive too
+    fn set_sensitive(&mut self, sensitive: bool) {
+        self.sensitive = sensitive;
+        if sensitive {
+            self.held_key = None;
+        } else {
+            // The trait method can't fail, so a key that can't be written is left out like a sensitive one
+            let _ = self.write_held_key();
+        }
+        self.inner.set_sensitive(sensitive)
+    }
+}
+
+/// * Feeds the input of a `Recording` into an app at the pace it was recorded, drawing through another backend
+///
+/// The app sees the recorded terminal size rather than the size of the inner backend, so layouts match the recording.
+/// Live input is ignored. Once the input runs out the last frame stays up until the time the recording ends, and then
+/// the app is told to quit, unless the inner backend quits first.
+pub struct ReplayBackend<B: Backend> {
+    inner: B,
+    events: VecDeque<(Duration, Event)>,
+    /// The time of the last event of any kind, output included
+    end: Duration,
+    size: (u16, u16),
+    speed: f64,
+    start: Option<Instant>,
+}
+
+impl<B: Backend> ReplayBackend<B> {
+
+    pub fn new(recording: &Recording, inner: B) -> Self {
+        Self {
+            inner,
+            events: recording.input_events().into(),
+            end: recording.events.last().map_or(Duration::ZERO, |(time, _)| *time),
+            size: (recording.width, recording.height),
+            speed: 1.0,
+            start: None,
+        }
+    }
+
+
+    /// Plays the recording faster (above 1.0) or slower (below 1.0)
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed.max(f64::EPSILON);
+        self
+    }
+
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+impl<B: Backend> Backend for ReplayBackend<B> {
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.inner.draw(content)
+    }
+
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.inner.move_cursor(x, y)
+    }
+
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.size)
+    }
+
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let due = self.events.front().map_or(self.end, |(time, _)| *time).div_f64(self.speed);
+
+        let elapsed = start.elapsed();
+        if elapsed < due {
+            let wait = (due - elapsed).min(timeout);
+            // Waiting on the inner backend rather than only sleeping lets it end the replay
+            let polled = Instant::now();
+            if let Some(Event::Quit) = self.inner.poll_event(wait)? {
+                return Ok(Some(Event::Quit));
+            }
+            std::thread::sleep(wait.saturating_sub(polled.elapsed()));
+            if start.elapsed() < due {
+                return Ok(None);
+            }
+        }
+
+        let Some((_, event)) = self.events.pop_front() else {
+            return Ok(Some(Event::Quit));
+        };
+        if let Event::Resize(width, height) = event {
+            self.size = (width, height);
+        }
+        Ok(Some(event))
+    }
+
+
+    fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.inner.set_color_depth(depth)
+    }
+
+
+    fn set_clipboard(&mut self, text: &str) -> io::Result<()> {
+        self.inner.set_clipboard(text)
+    }
+
+
+    fn set_sensitive(&mut self, sensitive: bool) {
+        self.inner.set_sensitive(sensitive)
+    }
+}
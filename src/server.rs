@@ -0,0 +1,263 @@
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use crate::{app::*, backend::AnsiBackend, color_depth::ColorDepth};
+
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const ECHO: u8 = 1;
+const SUPPRESS_GO_AHEAD: u8 = 3;
+const NAWS: u8 = 31;
+
+/// How long to wait before accepting again after a connection failed, so running out of file descriptors doesn't spin
+const ACCEPT_RETRY: Duration = Duration::from_millis(10);
+
+/// * Serves an `App` to remote terminals, giving every client its own session
+///
+/// Clients can connect with the bundled `tuitui-attach` binary (feature `server`),
+/// with `telnet` when telnet negotiation is enabled, or with any raw socket client.
+/// Window sizes are picked up from xterm size reports (`ESC [ 8 ; rows ; columns t`),
+/// which `tuitui-attach` sends on connect and on every resize, and from telnet NAWS.
+/// ```no_run
+/// # use tuitui::prelude::*;
+/// # struct Dashboard;
+/// # impl App for Dashboard {
+/// #     fn display(&mut self, ui: &mut Ui) {}
+/// #     fn update(&mut self, key: Option<Key>) -> bool { true }
+/// #     fn get_fps(&self) -> u32 { 30 }
+/// # }
+/// AppServer::new(|| Dashboard)
+///     .with_telnet(true)
+///     .serve_tcp("127.0.0.1:7000")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct AppServer<F> {
+    factory: F,
+    telnet: bool,
+    default_size: (u16, u16),
+    color_depth: ColorDepth,
+}
+
+impl<A, F> AppServer<F>
+where
+    A: App,
+    F: Fn() -> A + Send + Sync + 'static,
+{
+
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            telnet: false,
+            default_size: (80, 24),
+            color_depth: ColorDepth::TrueColor,
+        }
+    }
+
+
+    /// Negotiates character mode and window size with telnet clients
+    pub fn with_telnet(mut self, telnet: bool) -> Self {
+        self.telnet = telnet;
+        self
+    }
+
+
+    /// The size used until the client reports its own
+    pub fn with_default_size(mut self, width: u16, height: u16) -> Self {
+        self.default_size = (width, height);
+        self
+    }
+
+
+    /// The color depth of the clients' terminals, truecolor unless set
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_depth = depth;
+        self
+    }
+
+
+    /// Accepts clients until the process ends. Only failing to bind is returned, a connection that fails is dropped
+    pub fn serve_tcp<Addr: ToSocketAddrs>(self, addr: Addr) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        let server = Arc::new(self);
+
+        for stream in listener.incoming() {
+            // A failed connection only loses that client, the server keeps accepting
+            let stream: TcpStream = match stream {
+                Ok(stream) => stream,
+                Err(_) => {
+                    thread::sleep(ACCEPT_RETRY);
+                    continue;
+                }
+            };
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                let _ = stream.set_nodelay(true);
+                if let Ok(reader) = stream.try_clone() {
+                    let _ = server.serve_session(reader, &stream);
+                }
+                let _ = stream.shutdown(Shutdown::Both);
+            });
+        }
+        Ok(())
+    }
+
+
+    /// Like `serve_tcp`, on a Unix socket
+    #[cfg(unix)]
+    pub fn serve_unix<P: AsRef<std::path::Path>>(self, path: P) -> io::Result<()> {
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        let listener = UnixListener::bind(path)?;
+        let server = Arc::new(self);
+
+        for stream in listener.incoming() {
+            // A failed connection only loses that client, the server keeps accepting
+            let stream: UnixStream = match stream {
+                Ok(stream) => stream,
+                Err(_) => {
+                    thread::sleep(ACCEPT_RETRY);
+                    continue;
+                }
+            };
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                if let Ok(reader) = stream.try_clone() {
+                    let _ = server.serve_session(reader, &stream);
+                }
+                let _ = stream.shutdown(Shutdown::Both);
+            });
+        }
+        Ok(())
+    }
+
+
+    /// Runs one session of the app over any pair of streams, returning once the app quits or the client disconnects
+    pub fn serve_session<R, W>(&self, reader: R, mut writer: W) -> Result<(), Box<dyn std::error::Error>>
+    where
+        R: Read + Send + 'static,
+        W: Write,
+    {
+        if self.telnet {
+            writer.write_all(&[IAC, WILL, ECHO, IAC, WILL, SUPPRESS_GO_AHEAD, IAC, DO, NAWS])?;
+        }
+        // Switch to the alternate screen and ask the terminal for its size
+        writer.write_all(b"\x1b[?1049h\x1b[18t")?;
+        writer.flush()?;
+
+        let (width, height) = self.default_size;
+        let mut backend = AnsiBackend::new(writer, width, height).with_input(TelnetReader::new(reader));
+        // The client's terminal is unknown here, and terminals without hyperlinks skip them anyway
+        let runtime = AppRuntime::new().with_color_depth(self.color_depth).with_hyperlinks(true);
+        let result = runtime.run_with((self.factory)(), &mut backend);
+
+        let writer = backend.writer_mut();
+        writer.write_all(b"\x1b[0m\x1b[?1049l")?;
+        writer.flush()?;
+        result
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TelnetState {
+    Data,
+    CarriageReturn,
+    Command,
+    Option,
+    Subnegotiation,
+    SubnegotiationCommand,
+}
+
+/// * Strips telnet commands from a stream, turning NAWS window sizes into xterm size reports
+///
+/// Plain streams without any telnet commands pass through untouched, apart from `\r\n` and `\r\0` becoming `\r`.
+struct TelnetReader<R: Read> {
+    inner: R,
+    state: TelnetState,
+    subnegotiation: Vec<u8>,
+    filtered: Vec<u8>,
+}
+
+impl<R: Read> TelnetReader<R> {
+
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: TelnetState::Data,
+            subnegotiation: Vec::new(),
+            filtered: Vec::new(),
+        }
+    }
+
+
+    fn filter(&mut self, input: &[u8], output: &mut Vec<u8>) {
+        for &byte in input {
+            self.state = match (self.state, byte) {
+                (TelnetState::Data | TelnetState::CarriageReturn, IAC) => TelnetState::Command,
+                (TelnetState::CarriageReturn, b'\n' | 0) => TelnetState::Data,
+                (TelnetState::Data | TelnetState::CarriageReturn, b'\r') => {
+                    output.push(byte);
+                    TelnetState::CarriageReturn
+                }
+                (TelnetState::Data | TelnetState::CarriageReturn, _) => {
+                    output.push(byte);
+                    TelnetState::Data
+                }
+                (TelnetState::Command, IAC) => {
+                    output.push(IAC);
+                    TelnetState::Data
+                }
+                (TelnetState::Command, WILL | WONT | DO | DONT) => TelnetState::Option,
+                (TelnetState::Command, SB) => {
+                    self.subnegotiation.clear();
+                    TelnetState::Subnegotiation
+                }
+                (TelnetState::Command, _) | (TelnetState::Option, _) => TelnetState::Data,
+                (TelnetState::Subnegotiation, IAC) => TelnetState::SubnegotiationCommand,
+                (TelnetState::Subnegotiation, _) => {
+                    self.subnegotiation.push(byte);
+                    TelnetState::Subnegotiation
+                }
+                (TelnetState::SubnegotiationCommand, SE) => {
+                    if let [NAWS, w1, w0, h1, h0] = self.subnegotiation[..] {
+                        let width = u16::from_be_bytes([w1, w0]);
+                        let height = u16::from_be_bytes([h1, h0]);
+                        output.extend_from_slice(format!("\x1b[8;{};{}t", height, width).as_bytes());
+                    }
+                    TelnetState::Data
+                }
+                (TelnetState::SubnegotiationCommand, _) => {
+                    self.subnegotiation.push(byte);
+                    TelnetState::Subnegotiation
+                }
+            };
+        }
+    }
+}
+
+impl<R: Read> Read for TelnetReader<R> {
+
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut input = vec![0u8; buf.len().max(1)];
+        while self.filtered.is_empty() {
+            let n = self.inner.read(&mut input)?;
+            if n == 0 {
+                return Ok(0);
+            }
+            let mut filtered = std::mem::take(&mut self.filtered);
+            self.filter(&input[..n], &mut filtered);
+            self.filtered = filtered;
+        }
+
+        let len = self.filtered.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.filtered[..len]);
+        self.filtered.drain(..len);
+        Ok(len)
+    }
+}
@@ -0,0 +1,352 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use crate::{app::*, backend::*, buffer::{Buffer, Cell}, components::UnderlineStyle, event::Event, inputs::*, ui::Ui};
+
+/// * Drives an `App` frame by frame without a terminal
+///
+/// Input is scripted up front and consumed one event per frame, exactly like `AppRuntime` does,
+/// but no time passes between frames so tests stay deterministic:
+/// ```
+/// # use tuitui::prelude::*;
+/// struct Counter(u32);
+///
+/// impl App for Counter {
+///     fn display(&mut self, ui: &mut Ui) {
+///         ui.text(format!("Count: {}", self.0).into());
+///     }
+///
+///     fn update(&mut self, key: Option<Key>) -> bool {
+///         match key.map(|k| k.key) {
+///             Some(InputKey::Esc) => return false,
+///             Some(InputKey::Space) => self.0 += 1,
+///             _ => {}
+///         }
+///         true
+///     }
+///
+///     fn get_fps(&self) -> u32 {
+///         30
+///     }
+/// }
+///
+/// let mut runtime = TestRuntime::new(Counter(0), 20, 3);
+/// runtime.press(InputKey::Space).press(InputKey::Space).settle();
+/// assert_eq!(runtime.screen(), "Count: 2\n\n");
+///
+/// runtime.press(InputKey::Esc).settle();
+/// assert!(!runtime.is_running());
+/// ```
+pub struct TestRuntime<A: App> {
+    app: A,
+    backend: TestBackend,
+    frames: FrameLoop,
+    running: bool,
+}
+
+impl<A: App> TestRuntime<A> {
+
+    pub fn new(app: A, width: u16, height: u16) -> Self {
+        Self {
+            app,
+            backend: TestBackend::new(width, height),
+            frames: FrameLoop::new(),
+            running: true,
+        }
+    }
+
+
+    pub fn event(&mut self, event: Event) -> &mut Self {
+        self.backend.push_event(event);
+        self
+    }
+
+
+    pub fn key(&mut self, key: Key) -> &mut Self {
+        self.event(Event::Key(key))
+    }
+
+
+    pub fn press(&mut self, key: InputKey) -> &mut Self {
+        self.key(Key::new(key))
+    }
+
+
+    /// Queues one key per character; characters without an `InputKey` are skipped
+    pub fn type_str(&mut self, text: &str) -> &mut Self {
+        text.chars().filter_map(Key::from_char).for_each(|key| {
+            self.key(key);
+        });
+        self
+    }
+
+
+    pub fn resize(&mut self, width: u16, height: u16) -> &mut Self {
+        self.event(Event::Resize(width, height))
+    }
+
+
+    /// Queues every key and resize of a recording, ignoring its timing
+    #[cfg(feature = "record")]
+    pub fn replay(&mut self, recording: &crate::record::Recording) -> &mut Self {
+        for (_, event) in recording.input_events() {
+            self.event(event);
+        }
+        self
+    }
+
+
+    /// Runs a single frame, consuming at most one queued event. Returns `false` once the app has quit
+    pub fn step(&mut self) -> bool {
+        if self.running {
+            self.running = self.frames
+                .step(&mut self.app, &mut self.backend, Duration::ZERO)
+                .expect("the test backend never fails");
+        }
+        self.running
+    }
+
+
+    pub fn step_n(&mut self, frames: usize) -> bool {
+        for _ in 0..frames {
+            self.step();
+        }
+        self.running
+    }
+
+
+    /// Runs frames until every queued event has been handled, and at least one frame has been drawn
+    pub fn settle(&mut self) -> bool {
+        self.step();
+        while self.running && self.backend.pending_events() > 0 {
+            self.step();
+        }
+        self.running
+    }
+
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+
+    pub fn backend(&self) -> &TestBackend {
+        &self.backend
+    }
+
+
+    pub fn buffer(&self) -> &Buffer {
+        self.backend.buffer()
+    }
+
+
+    /// The text currently on screen, with trailing blanks trimmed from every line
+    pub fn screen(&self) -> String {
+        self.buffer().to_plain_string()
+    }
+
+
+    pub fn cell(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.buffer().get(x, y)
+    }
+}
+
+/// * Anything that can be turned into a frame for snapshot testing
+pub trait ToFrame {
+    fn to_frame(&self, width: u16, height: u16) -> Buffer;
+}
+
+impl ToFrame for Ui {
+
+    /// Revealed password inputs are masked again
+    fn to_frame(&self, width: u16, height: u16) -> Buffer {
+        self.to_public_buffer(width, height)
+    }
+}
+
+impl ToFrame for Buffer {
+
+    fn to_frame(&self, _width: u16, _height: u16) -> Buffer {
+        self.clone()
+    }
+}
+
+impl<A: App> ToFrame for TestRuntime<A> {
+
+    /// The screen, with revealed password inputs masked again
+    fn to_frame(&self, _width: u16, _height: u16) -> Buffer {
+        self.frames.public_frame().unwrap_or(self.buffer()).clone()
+    }
+}
+
+/// * The stored form of a frame: its text, plus a style layer mapping every styled cell to a legend entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub text: String,
+    pub styles: String,
+    pub legend: Vec<String>,
+}
+
+const STYLE_KEYS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+impl Snapshot {
+
+    pub fn from_buffer(buffer: &Buffer) -> Self {
+        let mut seen: Vec<String> = Vec::new();
+        let mut styles = Vec::new();
+
+        for y in 0..buffer.height() {
+            let mut row = String::new();
+            for x in 0..buffer.width() {
+                let cell = &buffer.cells()[y as usize * buffer.width() as usize + x as usize];
+                if !cell.is_styled() {
+                    row.push(' ');
+                    continue;
+                }
+                let description = describe_style(cell);
+                let index = seen.iter().position(|d| *d == description).unwrap_or_else(|| {
+                    seen.push(description);
+                    seen.len() - 1
+                });
+                row.push(STYLE_KEYS.chars().nth(index).unwrap_or('?'));
+            }
+            styles.push(row.trim_end().to_string());
+        }
+
+        let legend = seen
+            .iter()
+            .enumerate()
+            .map(|(i, d)| format!("{}: {}", STYLE_KEYS.chars().nth(i).unwrap_or('?'), d))
+            .collect();
+
+        Self {
+            text: buffer.to_plain_string(),
+            styles: styles.join("\n"),
+            legend,
+        }
+    }
+
+
+    pub fn to_file_contents(&self) -> String {
+        let mut output = String::new();
+        output.push_str("--- text\n");
+        output.push_str(&self.text);
+        output.push_str("\n--- styles\n");
+        output.push_str(&self.styles);
+        output.push_str("\n--- legend\n");
+        for entry in &self.legend {
+            output.push_str(entry);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn describe_style(cell: &Cell) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = cell.fg {
+        parts.push(format!("fg={:?}", fg));
+    }
+    if let Some(bg) = cell.bg {
+        parts.push(format!("bg={:?}", bg));
+    }
+    if let Some(underline) = cell.underline_color {
+        parts.push(format!("underline={:?}", underline));
+    }
+    parts.extend(cell.modifiers.iter().map(|m| format!("{:?}", m)));
+    if let Some(shape) = cell.underline_style.filter(|s| *s != UnderlineStyle::Single) {
+        parts.push(format!("underline_style={:?}", shape));
+    }
+    if let Some(url) = &cell.link {
+        parts.push(format!("link={}", url));
+    }
+    parts.join(" ")
+}
+
+/// Set this environment variable to write the current frames as the new golden files
+pub const UPDATE_SNAPSHOTS_VAR: &str = "TUITUI_UPDATE_SNAPSHOTS";
+
+thread_local! {
+    static SNAPSHOT_COUNTERS: RefCell<HashMap<String, usize>> = RefCell::new(HashMap::new());
+}
+
+/// Gives every unnamed snapshot within the same function its own number
+#[doc(hidden)]
+pub fn next_snapshot_name(function: &str) -> String {
+    let function = function.trim_end_matches("::f").trim_end_matches("::{{closure}}");
+    let base = function.split("::").skip(1).collect::<Vec<_>>().join("__");
+    let base = if base.is_empty() { function.to_string() } else { base };
+
+    SNAPSHOT_COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        let count = counters.entry(base.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 { base } else { format!("{}-{}", base, count) }
+    })
+}
+
+/// * Compares a frame against the golden file `<dir>/<name>.snap`, panicking with a diff when they differ
+///
+/// Usually called through `assert_frame_snapshot!`.
+#[track_caller]
+pub fn assert_snapshot(dir: &Path, name: &str, frame: &Buffer) {
+    let path = dir.join(format!("{}.snap", name));
+    let actual = Snapshot::from_buffer(frame).to_file_contents();
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some_and(|v| v != "0" && !v.is_empty());
+
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {}
+        result if update => {
+            fs::create_dir_all(dir).expect("failed to create the snapshot directory");
+            fs::write(&path, &actual).expect("failed to write the snapshot");
+            if result.is_ok() {
+                eprintln!("updated snapshot {}", path.display());
+            }
+        }
+        Ok(expected) => panic!(
+            "snapshot {} does not match the rendered frame:\n{}\nrun with {}=1 to accept the new frame",
+            path.display(),
+            line_diff(&expected, &actual),
+            UPDATE_SNAPSHOTS_VAR,
+        ),
+        Err(_) => panic!(
+            "snapshot {} does not exist, rendered frame:\n{}\nrun with {}=1 to create it",
+            path.display(),
+            actual,
+            UPDATE_SNAPSHOTS_VAR,
+        ),
+    }
+}
+
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let mut output = String::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => output.push_str(&format!("  {}\n", e)),
+            (e, a) => {
+                if let Some(e) = e {
+                    output.push_str(&format!("- {}\n", e));
+                }
+                if let Some(a) = a {
+                    output.push_str(&format!("+ {}\n", a));
+                }
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,151 @@
+use crate::components::{text::Color, Modifier, Style, WidgetStyle};
+
+/// * Named colors and styles the components draw with, so a whole app can be restyled at once
+///
+/// The `Ui` holds a theme, the plain `classic` one unless told otherwise. Components use it for anything they
+/// weren't given explicitly: headings and widget titles use `heading_style`, widgets use `border` and
+/// `border_style`, separators use `border`, and the screen is filled with `text` on `background` when those are set.
+/// ```
+/// # use tuitui::prelude::*;
+/// let mut ui = Ui::new().with_theme(Theme::high_contrast());
+/// ui.text(text!(span!("Saved", color ui.theme().success)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub primary: Color,
+    pub secondary: Color,
+    pub accent: Color,
+    /// The color of plain text, or the terminal's own when `None`
+    pub text: Option<Color>,
+    /// The color of the screen, or the terminal's own when `None`
+    pub background: Option<Color>,
+    /// Panels and other areas raised above the background
+    pub surface: Color,
+    /// The color of borders and separators, or the terminal's own when `None`
+    pub border: Option<Color>,
+    pub error: Color,
+    pub warning: Color,
+    pub success: Color,
+    pub muted: Color,
+    /// Whatever currently has the keyboard focus
+    pub focus: Color,
+    pub border_style: WidgetStyle,
+    /// Laid under headings and widget titles
+    pub heading_style: Style,
+}
+
+impl Default for Theme {
+
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Theme {
+
+    /// Square borders and bold headings in the terminal's own colors, like components drawn without a theme
+    pub fn classic() -> Self {
+        Self {
+            name: "classic".to_string(),
+            primary: Color::LightBlue,
+            secondary: Color::Magenta,
+            accent: Color::Yellow,
+            text: None,
+            background: None,
+            surface: Color::Black,
+            border: None,
+            error: Color::Red,
+            warning: Color::Yellow,
+            success: Color::Green,
+            muted: Color::Gray,
+            focus: Color::Cyan,
+            border_style: WidgetStyle::tuitui_classic(),
+            heading_style: Style::from(Modifier::Bold),
+        }
+    }
+
+
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            primary: Color::RGB(164, 143, 214),
+            secondary: Color::RGB(122, 98, 168),
+            accent: Color::RGB(245, 235, 220),
+            text: None,
+            background: None,
+            surface: Color::RGB(40, 36, 52),
+            border: Some(Color::RGB(110, 104, 128)),
+            error: Color::RGB(240, 98, 98),
+            warning: Color::RGB(240, 190, 90),
+            success: Color::RGB(120, 200, 120),
+            muted: Color::RGB(140, 136, 150),
+            focus: Color::RGB(120, 180, 255),
+            border_style: WidgetStyle::tuitui_rounded(),
+            heading_style: Style::from(Modifier::Bold).with_fg(Color::RGB(164, 143, 214)),
+        }
+    }
+
+
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            primary: Color::RGB(92, 68, 150),
+            secondary: Color::RGB(122, 98, 168),
+            accent: Color::RGB(176, 92, 40),
+            text: Some(Color::RGB(36, 32, 44)),
+            background: Some(Color::RGB(250, 248, 244)),
+            surface: Color::RGB(236, 232, 242),
+            border: Some(Color::RGB(160, 154, 172)),
+            error: Color::RGB(190, 30, 40),
+            warning: Color::RGB(150, 100, 0),
+            success: Color::RGB(30, 120, 50),
+            muted: Color::RGB(110, 106, 120),
+            focus: Color::RGB(20, 100, 210),
+            border_style: WidgetStyle::tuitui_rounded(),
+            heading_style: Style::from(Modifier::Bold).with_fg(Color::RGB(92, 68, 150)),
+        }
+    }
+
+
+    /// Pure colors from the 16 color palette and heavy borders, readable on any terminal
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high contrast".to_string(),
+            primary: Color::BrightWhite,
+            secondary: Color::LightCyan,
+            accent: Color::Banana,
+            text: Some(Color::BrightWhite),
+            background: Some(Color::Black),
+            surface: Color::Black,
+            border: Some(Color::BrightWhite),
+            error: Color::BrightRed,
+            warning: Color::Banana,
+            success: Color::Lime,
+            muted: Color::White,
+            focus: Color::Banana,
+            border_style: WidgetStyle::tuitui_heavy_box(),
+            heading_style: Style::from(Modifier::Bold).with_modifier(Modifier::Underlined).with_fg(Color::BrightWhite),
+        }
+    }
+
+
+    /// Picks a built-in theme by name, like `WidgetStyle::from_name`. Unknown names get `Theme::default`
+    pub fn from_name(name: &str) -> Self {
+        Self::try_from_name(name).unwrap_or_default()
+    }
+
+
+    /// Like `from_name`, but `None` for names it doesn't know
+    pub fn try_from_name(name: &str) -> Option<Self> {
+        let n = name.to_lowercase().replace(['-', '_'], " ");
+        let theme = match n.strip_prefix("tuitui ").unwrap_or(&n) {
+            "classic" | "default" | "plain" => Self::classic(),
+            "dark" | "night" => Self::dark(),
+            "light" | "day" => Self::light(),
+            "high contrast" | "contrast" => Self::high_contrast(),
+            _ => return None,
+        };
+        Some(theme)
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use serde::Deserialize;
+use crate::components::{text::Color, Modifiers, WidgetStyle};
+use crate::{markup, theme::Theme};
+
+/// * Describes why a theme file couldn't be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeError {
+    pub details: String,
+}
+
+impl fmt::Display for ThemeError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// What a theme file holds. Everything is optional and falls back to the `base` theme
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    name: Option<String>,
+    primary: Option<String>,
+    secondary: Option<String>,
+    accent: Option<String>,
+    text: Option<String>,
+    background: Option<String>,
+    surface: Option<String>,
+    border: Option<String>,
+    error: Option<String>,
+    warning: Option<String>,
+    success: Option<String>,
+    muted: Option<String>,
+    focus: Option<String>,
+    border_style: Option<String>,
+    heading_style: Option<Vec<String>>,
+}
+
+fn color(field: &str, value: &str) -> Result<Color, ThemeError> {
+    value.parse().map_err(|e| ThemeError { details: format!("`{}`: {}", field, e) })
+}
+
+/// `text`, `background` and `border` also take `"none"`, for the terminal's own color
+fn optional_color(field: &str, value: &str) -> Result<Option<Color>, ThemeError> {
+    match value.trim().to_lowercase().as_str() {
+        "none" | "default" | "terminal" => Ok(None),
+        _ => color(field, value).map(Some),
+    }
+}
+
+impl ThemeFile {
+
+    fn into_theme(self) -> Result<Theme, ThemeError> {
+        let mut theme = match self.base.as_deref() {
+            Some(name) => Theme::try_from_name(name).ok_or_else(|| ThemeError {
+                details: format!("`base`: `{}` isn't a built-in theme", name),
+            })?,
+            None => Theme::default(),
+        };
+        let base_primary = theme.primary;
+        if let Some(name) = self.name {
+            theme.name = name;
+        }
+
+        let colors = [
+            ("primary", self.primary, &mut theme.primary),
+            ("secondary", self.secondary, &mut theme.secondary),
+            ("accent", self.accent, &mut theme.accent),
+            ("surface", self.surface, &mut theme.surface),
+            ("error", self.error, &mut theme.error),
+            ("warning", self.warning, &mut theme.warning),
+            ("success", self.success, &mut theme.success),
+            ("muted", self.muted, &mut theme.muted),
+            ("focus", self.focus, &mut theme.focus),
+        ];
+        for (field, value, target) in colors {
+            if let Some(value) = value {
+                *target = color(field, &value)?;
+            }
+        }
+        // Headings drawn in the primary color of the base theme follow the new one
+        if theme.heading_style.fg == Some(base_primary) {
+            theme.heading_style.fg = Some(theme.primary);
+        }
+        if let Some(value) = self.border {
+            theme.border = optional_color("border", &value)?;
+        }
+        if let Some(value) = self.text {
+            theme.text = optional_color("text", &value)?;
+        }
+        if let Some(value) = self.background {
+            theme.background = optional_color("background", &value)?;
+        }
+
+        if let Some(name) = self.border_style {
+            theme.border_style = WidgetStyle::try_from_name(&name).ok_or_else(|| ThemeError {
+                details: format!("`border_style`: `{}` isn't a border style", name),
+            })?;
+        }
+        if let Some(names) = self.heading_style {
+            theme.heading_style.add_modifier = names
+                .iter()
+                .map(|n| markup::modifier_named(n).ok_or_else(|| ThemeError {
+                    details: format!("`heading_style`: `{}` isn't a text style", n),
+                }))
+                .collect::<Result<_, _>>()?;
+            theme.heading_style.sub_modifier = Modifiers::empty();
+        }
+        Ok(theme)
+    }
+}
+
+impl Theme {
+
+    /// * Reads a theme from TOML
+    ///
+    /// Every field is optional and falls back to the theme named by `base` (`Theme::default` if left out). Colors take the
+    /// same forms as markup tags, the border style takes the names of `WidgetStyle::from_name`, and the
+    /// heading style takes a list of names like `bold` or `underline`. Names it doesn't know are errors, like
+    /// fields it doesn't know.
+    /// ```
+    /// # use tuitui::prelude::*;
+    /// let theme = Theme::from_toml(r##"
+    ///     base = "light"
+    ///     name = "sunset"
+    ///     primary = "#ff7e5f"
+    ///     border = "grey"
+    ///     border_style = "double"
+    ///     heading_style = ["bold", "italic"]
+    /// "##).unwrap();
+    /// assert_eq!(theme.primary, Color::RGB(255, 126, 95));
+    /// assert_eq!(theme.text, Theme::light().text);
+    ///
+    /// assert!(Theme::from_toml(r#"base = "sepia""#).is_err());
+    /// ```
+    pub fn from_toml(source: &str) -> Result<Theme, ThemeError> {
+        toml::from_str::<ThemeFile>(source)
+            .map_err(|e| ThemeError { details: e.message().to_string() })?
+            .into_theme()
+    }
+
+
+    /// * Reads a theme from JSON, with the same fields as `from_toml`
+    pub fn from_json(source: &str) -> Result<Theme, ThemeError> {
+        serde_json::from_str::<ThemeFile>(source)
+            .map_err(|e| ThemeError { details: e.to_string() })?
+            .into_theme()
+    }
+
+
+    /// * Loads a `.json` theme file, or a TOML one for any other extension
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Theme, ThemeError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| ThemeError {
+            details: format!("couldn't read {}: {}", path.display(), e),
+        })?;
+        let is_json = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json"));
+        if is_json { Self::from_json(&source) } else { Self::from_toml(&source) }
+    }
+}
+
+/// Reloads a theme file whenever its modification time changes
+pub(crate) struct ThemeWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ThemeWatcher {
+
+    /// Loads the theme a first time, so a broken file is reported before the app starts
+    pub(crate) fn new(path: PathBuf) -> Result<(Self, Theme), ThemeError> {
+        let theme = Theme::load(&path)?;
+        let modified = Self::modified(&path);
+        Ok((Self { path, modified }, theme))
+    }
+
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+
+    /// The reloaded theme if the file changed, or why it couldn't be loaded. A file that doesn't load, like one
+    /// saved halfway, is reported once and tried again when it changes
+    pub(crate) fn poll(&mut self) -> Option<Result<Theme, ThemeError>> {
+        let modified = Self::modified(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(Theme::load(&self.path))
+    }
+}
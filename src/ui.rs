@@ -1,48 +1,183 @@
 use crate::components::*;
+use crate::{buffer::Buffer, theme::Theme};
 
 /// * Builds your user interface with a fluent API.
 ///
 /// Add components in the order they should appear:
 /// ```no_run
+/// # use tuitui::prelude::*;
 /// let mut ui = Ui::new();
 /// ui
-///  .heading("Welcome")
-///  .paragraph("This is my app")
-///  .widget(some_component);
+///  .heading("Welcome".into())
+///  .paragraph("This is my app".into())
+///  .widget(|w| w.with_contents("Hi".into()).build());
 /// ```
+#[derive(Default)]
 pub struct Ui {
-    pub(crate) components: Vec<Box<dyn Component>>
+    pub(crate) components: Vec<Box<dyn Component>>,
+    pub(crate) width: Option<u16>,
+    pub(crate) theme: Theme,
+    /// Writes link targets after the link text, for terminals without hyperlinks
+    pub(crate) inline_links: bool,
+    /// Text to put on the clipboard once the frame is drawn
+    pub(crate) clipboard: Option<String>,
+    /// Set while a password input is focused or revealed, see `Backend::set_sensitive`
+    pub(crate) sensitive: bool,
+    /// What recordings and snapshots show in place of revealed password inputs, by component index
+    pub(crate) masked: Vec<(usize, Text)>,
+    /// Why the theme file last failed to reload, until it loads again
+    #[cfg(feature = "theme-files")]
+    pub(crate) theme_error: Option<crate::theme_file::ThemeError>,
+}
+
+/// A heading, styled by the theme when there is one
+struct Heading(Text);
+
+impl Component for Heading {
+
+    fn render(&self) -> String {
+        self.0.inherit(Style::from(Modifier::Bold)).render()
+    }
+
+
+    fn render_themed(&self, theme: &Theme) -> String {
+        self.0.inherit(theme.heading_style).render()
+    }
 }
 
 impl Ui {
     
     pub fn new() -> Self {
-        Self { components: Vec::new() }
+        Self {
+            components: Vec::new(),
+            width: None,
+            theme: Theme::default(),
+            inline_links: false,
+            clipboard: None,
+            sensitive: false,
+            masked: Vec::new(),
+            #[cfg(feature = "theme-files")]
+            theme_error: None,
+        }
+    }
+
+
+    /// Draws the components with `theme` instead of the plain `Theme::classic`
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme; self
+    }
+
+
+    /// Switches the theme. It's kept between frames, so an app can call this from `display` once
+    pub fn set_theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+
+    /// * Why the theme file of `AppRuntime::with_theme_file` couldn't be reloaded, until it loads again
+    ///
+    /// The last theme that loaded stays in use meanwhile, so an app can show this wherever it suits it.
+    #[cfg(feature = "theme-files")]
+    pub fn theme_error(&self) -> Option<&crate::theme_file::ThemeError> {
+        self.theme_error.as_ref()
+    }
+
+
+    /// Sets the width paragraphs are wrapped to. The runtime sets it to the width of the screen before every frame
+    pub fn with_width(mut self, width: u16) -> Self {
+        self.width = Some(width); self
+    }
+
+
+    pub fn width(&self) -> Option<u16> {
+        self.width
+    }
+
+
+    /// Whether the terminal shows OSC 8 hyperlinks. When it doesn't, every link in the components added after this is
+    /// followed by its URL, like `docs (https://docs.rs/tuitui)` (see `Text::inline_links`). Components added with
+    /// `component` are drawn as they are. The runtime guesses it from the environment unless told otherwise
+    pub fn with_hyperlinks(mut self, supported: bool) -> Self {
+        self.inline_links = !supported; self
+    }
+
+
+    pub fn set_hyperlinks(&mut self, supported: bool) -> &mut Self {
+        self.inline_links = !supported;
+        self
     }
     
     
     pub fn text(&mut self, content: Text) -> &mut Self {
+        let content = self.links(content);
         self.components.push(Box::new(content));
         self
     }
+
+
+    /// Writes the targets of the links in `text` after them when the terminal can't show hyperlinks
+    fn links(&self, text: Text) -> Text {
+        if self.inline_links { text.inline_links() } else { text }
+    }
     
     
     pub fn separator(&mut self, pattern: Text, repeat: usize) -> &mut Self {
-        let text: Text = pattern.into();
-        self.components.push(Box::new(Separator::new(text, repeat)));
+        let pattern = self.links(pattern);
+        self.components.push(Box::new(Separator::new(pattern, repeat)));
+        self
+    }
+    
+    
+    /// Adds a separator repeating `pattern` across exactly `width` columns
+    pub fn separator_fill(&mut self, pattern: Text, width: usize) -> &mut Self {
+        let pattern = self.links(pattern);
+        self.components.push(Box::new(Separator::fill(pattern, width)));
         self
     }
     
     
+    /// Adds text wrapped between words to the width of the `Ui`, if it's known
     pub fn paragraph(&mut self, content: Text) -> &mut Self {
-        self.text(content)
+        self.paragraph_with(content, WrapOptions::default())
+    }
+
+
+    /// Adds text wrapped and then aligned within the width of the `Ui`, if it's known
+    pub fn paragraph_aligned(&mut self, content: Text, alignment: Alignment) -> &mut Self {
+        let content = self.links(content);
+        let width = self.width.map_or(content.width(), |w| w as usize);
+        self.component(content.wrap(width).align(alignment, width))
+    }
+
+
+    pub fn paragraph_with(&mut self, content: Text, options: WrapOptions) -> &mut Self {
+        let content = self.links(content);
+        match self.width {
+            Some(width) => self.component(content.wrap_with(width as usize, options)),
+            None => self.component(content),
+        }
     }
     
     
+    /// Adds a heading, in the heading style of the theme
     pub fn heading(&mut self, content: Text) -> &mut Self {
-        let mut text: Text = content.into();
-        text.bold_all();
-        self.components.push(Box::new(text));
+        let content = self.links(content);
+        self.components.push(Box::new(Heading(content)));
+        self
+    }
+
+
+    /// Adds a heading aligned within the width of the `Ui`, or within its own widest line if that isn't known
+    pub fn heading_aligned(&mut self, content: Text, alignment: Alignment) -> &mut Self {
+        let content = self.links(content);
+        let width = self.width.map_or(content.width(), |w| w as usize);
+        self.components.push(Box::new(Heading(content.align(alignment, width))));
         self
     }
 
@@ -51,7 +186,7 @@ impl Ui {
     where
         F: FnOnce(WidgetBuilder) -> Widget
     {
-        let builder = Widget::new();
+        let builder = Widget::new().with_inline_links(self.inline_links);
         self.components.push(Box::new(build(builder)));
         self
     }
@@ -63,11 +198,83 @@ impl Ui {
     }
 
     
+    /// Adds source code highlighted with the colors of the theme, see `Code` for line numbers and scrolling
+    #[cfg(feature = "code")]
+    pub fn code(&mut self, source: &str, language: Language) -> &mut Self {
+        self.components.push(Box::new(Code::new(source, language)));
+        self
+    }
+
+
+    /// Adds Markdown, with paragraphs wrapped to the width of the `Ui` if it's known
+    #[cfg(feature = "markdown")]
+    pub fn markdown(&mut self, source: &str) -> &mut Self {
+        let markdown = Markdown::new(source).with_inline_links(self.inline_links);
+        match self.width {
+            Some(width) => self.component(markdown.with_width(width as usize)),
+            None => self.component(markdown),
+        }
+    }
+
+
+    /// Adds a text area, and sends whatever was copied or cut in it since the last frame to the system clipboard
+    pub fn text_area(&mut self, area: &mut TextArea) -> &mut Self {
+        if let Some(copied) = area.take_copied() {
+            self.copy_to_clipboard(&copied);
+        }
+        self.component(area.clone())
+    }
+
+
+    /// * Adds a password input
+    ///
+    /// While it's focused or revealed the frame counts as sensitive: recordings leave out the frames drawn and the
+    /// keys pressed until it's gone. Snapshots always show the mask, even when the value is revealed on screen.
+    pub fn password_input(&mut self, input: &PasswordInput) -> &mut Self {
+        if input.is_focused() || input.is_revealed() {
+            self.sensitive = true;
+        }
+        if input.is_revealed() {
+            self.masked.push((self.components.len(), input.masked_text(&self.theme)));
+        }
+        self.component(input.to_text(&self.theme))
+    }
+
+
+    /// Puts text on the system clipboard after this frame, on terminals that support OSC 52
+    pub fn copy_to_clipboard(&mut self, text: &str) -> &mut Self {
+        self.clipboard = Some(text.to_string());
+        self
+    }
+
+
+    /// Adds any component, like one built with options the shorthands above don't take
+    pub fn component<C: Component + 'static>(&mut self, component: C) -> &mut Self {
+        self.components.push(Box::new(component));
+        self
+    }
+
+    
     pub fn render(&self) -> String {
+        self.render_frame(false)
+    }
+
+
+    /// Renders with every revealed password input masked again, for recordings and snapshots
+    pub(crate) fn render_public(&self) -> String {
+        self.render_frame(true)
+    }
+
+
+    fn render_frame(&self, public: bool) -> String {
         let mut buffer = String::new();
         
-        for component in &self.components {
-            buffer.push_str(&component.render());
+        for (i, component) in self.components.iter().enumerate() {
+            let output = match self.masked.iter().find(|(index, _)| *index == i) {
+                Some((_, masked)) if public => masked.render(),
+                _ => component.render_themed(&self.theme),
+            };
+            buffer.push_str(&output);
             buffer.push('\n');
         }
         
@@ -75,7 +282,39 @@ impl Ui {
     }
     
     
+    /// Removes every component, keeping the width and the theme
     pub fn clear(&mut self) {
         self.components.clear();
+        self.masked.clear();
+        self.sensitive = false;
+    }
+
+
+    /// Renders into a buffer, filling every cell left uncolored with the text and background colors of the theme
+    pub(crate) fn to_buffer(&self, width: u16, height: u16) -> Buffer {
+        self.fill(Buffer::from_ansi(&self.render(), width, height))
+    }
+
+
+    /// Like `to_buffer`, but with revealed password inputs masked
+    pub(crate) fn to_public_buffer(&self, width: u16, height: u16) -> Buffer {
+        self.fill(Buffer::from_ansi(&self.render_public(), width, height))
+    }
+
+
+    fn fill(&self, mut buffer: Buffer) -> Buffer {
+        if self.theme.text.is_none() && self.theme.background.is_none() {
+            return buffer;
+        }
+        let (width, height) = buffer.size();
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(cell) = buffer.get_mut(x, y) {
+                    cell.fg = cell.fg.or(self.theme.text);
+                    cell.bg = cell.bg.or(self.theme.background);
+                }
+            }
+        }
+        buffer
     }
 }
\ No newline at end of file
@@ -0,0 +1,62 @@
+//! # Width
+//!
+//! Measures strings in terminal columns instead of bytes or chars.
+//!
+//! Text is split into grapheme clusters (what a reader sees as one character, like `e` with a combining
+//! accent or a family emoji), and every cluster takes 0, 1 or 2 columns according to its East Asian width:
+//! ```
+//! # use tuitui::width;
+//! assert_eq!(width::str_width("tuitui"), 6);
+//! assert_eq!(width::str_width("トゥイ"), 6);
+//! assert_eq!(width::str_width("e\u{301}"), 1);
+//! assert_eq!(width::truncate("トゥイ", 5), "トゥ");
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// * Splits a string into grapheme clusters
+pub fn graphemes(s: &str) -> impl Iterator<Item = &str> {
+    s.graphemes(true)
+}
+
+/// * The number of columns a single grapheme cluster takes, at most 2
+///
+/// Control characters take no columns.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.chars().next().is_none_or(char::is_control) {
+        return 0;
+    }
+    grapheme.width().min(2)
+}
+
+/// * The number of columns a string takes on a single line
+pub fn str_width(s: &str) -> usize {
+    graphemes(s).map(grapheme_width).sum()
+}
+
+/// * The number of columns taken by the widest line of a string
+pub fn max_line_width(s: &str) -> usize {
+    s.lines().map(str_width).max().unwrap_or(0)
+}
+
+/// * The longest prefix of a string that fits in `width` columns, never splitting a grapheme cluster
+pub fn truncate(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, grapheme) in s.grapheme_indices(true) {
+        used += grapheme_width(grapheme);
+        if used > width {
+            return &s[..i];
+        }
+    }
+    s
+}
+
+/// * Truncates or pads a string with spaces so it takes exactly `width` columns
+///
+/// A double width character that would only half fit is replaced by a space.
+pub fn fit(s: &str, width: usize) -> String {
+    let truncated = truncate(s, width);
+    let padding = width - str_width(truncated);
+    format!("{}{}", truncated, " ".repeat(padding))
+}
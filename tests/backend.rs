@@ -0,0 +1,32 @@
+use tuitui::prelude::*;
+
+fn frame() -> Buffer {
+    Buffer::from_ansi(
+        "\x1b[1;31mbold red\x1b[0m plain \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\\n\x1b[4:3;58;2;0;128;255m漢字\x1b[0m",
+        24,
+        2,
+    )
+}
+
+#[test]
+fn crossterm_writes_the_same_cells_as_the_other_backends() {
+    let frame = frame();
+    let mut backend = CrosstermBackend::with_writer(Vec::new());
+    backend.set_color_depth(ColorDepth::TrueColor);
+    backend.draw(frame.content()).unwrap();
+
+    assert_eq!(String::from_utf8(backend.writer().clone()).unwrap(), frame.to_ansi());
+}
+
+
+#[test]
+fn crossterm_reduces_colors_to_its_depth() {
+    let frame = frame();
+    let mut backend = CrosstermBackend::with_writer(Vec::new());
+    backend.set_color_depth(ColorDepth::Ansi16);
+    backend.draw(frame.content()).unwrap();
+    let output = String::from_utf8(backend.writer().clone()).unwrap();
+
+    assert!(output.contains("\x1b[4m漢字"));
+    assert!(!output.contains("58;2;"));
+}
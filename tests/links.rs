@@ -0,0 +1,53 @@
+use tuitui::prelude::*;
+
+fn linked() -> Text {
+    text!(span!("see "), span!("docs", link "https://x.y", color Color::Red), span!(" next", bold))
+}
+
+#[test]
+fn link_targets_are_written_as_plain_spans() {
+    let mut ui = Ui::new().with_hyperlinks(false);
+    ui.text(linked());
+    assert_eq!(
+        ui.render(),
+        "see \x1b[31m\x1b]8;;https://x.y\x1b\\docs\x1b[0m\x1b]8;;\x1b\\ (https://x.y)\x1b[1m next\x1b[0m\n",
+    );
+}
+
+#[test]
+fn link_targets_fit_inside_widgets() {
+    let mut ui = Ui::new().with_hyperlinks(false);
+    ui.widget(|w| w.with_contents(linked()).with_height(3).build());
+    let screen = ui.to_frame(40, 3).to_plain_string();
+    assert_eq!(screen, "┌───────────────────────────┐\n│see docs (https://x.y) next│\n└───────────────────────────┘");
+
+    let mut ui = Ui::new().with_hyperlinks(false);
+    ui.widget(|w| w.with_contents(linked()).with_width(31).with_height(3).with_alignment(Alignment::Center).build());
+    let screen = ui.to_frame(40, 3).to_plain_string();
+    assert_eq!(screen, "┌─────────────────────────────┐\n│ see docs (https://x.y) next │\n└─────────────────────────────┘");
+}
+
+#[test]
+fn link_targets_are_wrapped_with_the_paragraph() {
+    let mut ui = Ui::new().with_hyperlinks(false).with_width(16);
+    ui.paragraph(linked());
+    let screen = ui.to_frame(16, 3).to_plain_string();
+    assert_eq!(screen, "see docs\n(https://x.y)\nnext");
+}
+
+#[test]
+fn links_stay_hyperlinks_on_capable_terminals() {
+    let mut ui = Ui::new().with_hyperlinks(true);
+    ui.text(linked());
+    assert!(!ui.render().contains("(https://x.y)"));
+    assert_eq!(ui.to_frame(20, 1).get(4, 0).unwrap().link.as_deref(), Some("https://x.y"));
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn markdown_link_targets_are_wrapped_too() {
+    let mut ui = Ui::new().with_hyperlinks(false).with_width(16);
+    ui.markdown("see **[docs](https://x.y)** next");
+    let screen = ui.to_frame(16, 3).to_plain_string();
+    assert_eq!(screen, "see docs\n(https://x.y)\nnext");
+}
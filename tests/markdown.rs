@@ -0,0 +1,134 @@
+#![cfg(feature = "markdown")]
+
+use tuitui::prelude::*;
+
+fn plain(source: &str) -> String {
+    Markdown::new(source).to_text(&Theme::dark()).render_plain()
+}
+
+
+/// The style of the first span holding `content`
+fn style_of(text: &Text, content: &str) -> Style {
+    text.spans().iter().find(|s| s.render_plain() == content).map(|s| s.get_style()).unwrap()
+}
+
+#[test]
+fn tables_align_their_columns() {
+    let source = "| Name | Qty | Note |\n|:-----|----:|:----:|\n| apple | 3 | ok |\n| kiwi | 12 | fine |";
+    assert_eq!(
+        plain(source),
+        "Name  │ Qty │ Note\n\
+         ──────┼─────┼─────\n\
+         apple │   3 │  ok\n\
+         kiwi  │  12 │ fine"
+    );
+
+    let theme = Theme::dark();
+    let text = Markdown::new(source).to_text(&theme);
+    assert_eq!(style_of(&text, "Name"), theme.heading_style);
+    assert!(style_of(&text, "apple").is_plain());
+}
+
+
+#[test]
+fn nested_lists_indent_under_their_marker() {
+    assert_eq!(
+        plain("- one\n  - nested\n    1. deep\n    2. deeper\n- two"),
+        "• one\n  ◦ nested\n    1. deep\n    2. deeper\n• two"
+    );
+    assert_eq!(plain("3. three\n4. four"), "3. three\n4. four");
+}
+
+
+#[test]
+fn block_quotes_prefix_every_line() {
+    assert_eq!(
+        plain("> quoted\n> > inner\n> - in quote"),
+        "│ quoted\n│\n│ │ inner\n│\n│ • in quote"
+    );
+    // Wrapping leaves room for the quote bar
+    assert_eq!(
+        Markdown::new("> one two three").with_width(9).to_text(&Theme::dark()).render_plain(),
+        "│ one two\n│ three"
+    );
+}
+
+
+#[test]
+fn fenced_code_is_drawn_in_a_widget() {
+    assert_eq!(
+        plain("```rust\nlet x = 1;\n```\n\n```\nplain\n```"),
+        "╭─ rust ───╮\n\
+         │let x = 1;│\n\
+         ╰──────────╯\n\
+         \n\
+         ╭─────╮\n\
+         │plain│\n\
+         ╰─────╯"
+    );
+    // A title wider than the code widens the box
+    assert_eq!(plain("```toml\nx\n```"), "╭─ toml ─╮\n│x       │\n╰────────╯");
+}
+
+
+#[cfg(feature = "code")]
+#[test]
+fn fenced_code_is_highlighted() {
+    let theme = Theme::dark();
+    let text = Markdown::new("```rust\nlet x = 1;\n```").to_text(&theme);
+    assert!(style_of(&text, "let").has(Modifier::Bold));
+    assert_eq!(style_of(&text, "1").fg, Some(theme.warning));
+}
+
+
+#[test]
+fn horizontal_rules_span_the_width() {
+    assert_eq!(plain("above\n\n---\n\nbelow"), format!("above\n\n{}\n\nbelow", "─".repeat(40)));
+    assert_eq!(
+        Markdown::new("a\n\n***\n\nb").with_width(12).to_text(&Theme::dark()).render_plain(),
+        format!("a\n\n{}\n\nb", "─".repeat(12))
+    );
+}
+
+
+#[test]
+fn links_emit_osc8_hyperlinks() {
+    let text = Markdown::new("See [docs](https://docs.rs) now").to_text(&Theme::dark());
+    assert_eq!(text.render_plain(), "See docs now");
+    let link = text.spans().iter().find(|s| s.render_plain() == "docs").unwrap();
+    assert_eq!(link.get_link(), Some("https://docs.rs"));
+
+    let rendered = text.render();
+    assert!(rendered.contains("\x1b]8;;https://docs.rs\x1b\\docs"));
+    assert!(rendered.ends_with("\x1b]8;;\x1b\\ now"));
+}
+
+
+#[test]
+fn links_reach_the_frame() {
+    let mut ui = Ui::new().with_hyperlinks(true);
+    ui.markdown("See [docs](https://docs.rs) now");
+    let frame = ui.to_frame(20, 1);
+    assert_eq!(frame.get(3, 0).unwrap().link, None);
+    assert_eq!(frame.get(4, 0).unwrap().link.as_deref(), Some("https://docs.rs"));
+    assert_eq!(frame.get(8, 0).unwrap().link, None);
+
+    // Terminals without hyperlinks get the target written out instead
+    let mut ui = Ui::new().with_hyperlinks(false);
+    ui.markdown("See [docs](https://docs.rs) now");
+    assert_eq!(ui.to_frame(40, 1).to_plain_string(), "See docs (https://docs.rs) now");
+}
+
+
+#[test]
+fn top_level_headings_as_ascii_art() {
+    let theme = Theme::dark();
+    let text = Markdown::new("# Hi\n\n## Sub").with_ascii_headings(true).to_text(&theme);
+    assert_eq!(
+        text.render_plain(),
+        "  _   _   _ \n | | | | (_)\n | |_| | | |\n |  _  | | |\n |_| |_| |_|\n\nSub"
+    );
+    assert_eq!(style_of(&text, "  _   _   _ ").fg, Some(theme.primary));
+
+    assert_eq!(plain("# Hi\n\n## Sub"), "Hi\n\nSub");
+}
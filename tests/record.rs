@@ -0,0 +1,134 @@
+#![cfg(feature = "record")]
+
+use std::time::{Duration, Instant};
+use tuitui::prelude::*;
+
+const HEADER: &str = "{\"version\": 2, \"width\": 40, \"height\": 10}";
+
+fn parse_error(cast: &str) -> String {
+    Recording::parse(cast).unwrap_err().to_string()
+}
+
+#[test]
+fn parses_events_and_skips_markers() {
+    let cast = format!(
+        "{}\n[0.5, \"i\", \"a\"]\n\n[1.0, \"m\", \"chapter\"]\n[1.5, \"r\", \"20x5\"]\n[2.0, \"o\", \"\\u001b[1mhi\\n\"]\n",
+        HEADER
+    );
+    let recording = Recording::parse(&cast).unwrap();
+    assert_eq!((recording.width, recording.height), (40, 10));
+    assert_eq!(
+        recording.events,
+        [
+            (Duration::from_millis(500), CastEvent::Input("a".into())),
+            (Duration::from_millis(1500), CastEvent::Resize(20, 5)),
+            (Duration::from_secs(2), CastEvent::Output("\x1b[1mhi\n".into())),
+        ]
+    );
+}
+
+#[test]
+fn header_fields_it_doesnt_use_are_ignored() {
+    let cast = "{\"version\": 2, \"width\": 3, \"height\": 4, \"idle_time_limit\": null, \"env\": {\"TERM\": \"xterm\"}, \"x\": [true, false]}";
+    let recording = Recording::parse(cast).unwrap();
+    assert_eq!((recording.width, recording.height), (3, 4));
+    assert!(recording.events.is_empty());
+}
+
+#[test]
+fn malformed_headers_are_errors() {
+    assert!(parse_error("").contains("header"));
+    assert!(parse_error("{\"version\": 2, \"width\": 40").contains("header"));
+    assert!(parse_error("[2, 40, 10]").contains("version"));
+    assert!(parse_error("{\"version\": 1, \"width\": 40, \"height\": 10}").contains("version"));
+    assert!(parse_error("{\"version\": 2, \"height\": 10}").contains("width"));
+    assert!(parse_error("{\"version\": 2, \"width\": 70000, \"height\": 10}").contains("width"));
+    assert!(parse_error("{\"version\": 2, \"width\": 40, \"height\": \"10\"}").contains("height"));
+}
+
+#[test]
+fn malformed_events_are_errors() {
+    for event in [
+        "[0.5, \"i\"",
+        "[0.5, \"i\", \"a\"] trailing",
+        "{\"time\": 0.5}",
+        "[\"0.5\", \"i\", \"a\"]",
+        "[0.5, \"i\", 7]",
+        "[0.5, \"i\", \"\\ud800\"]",
+        "[0.5, \"r\", \"20\"]",
+        "[0.5, \"r\", \"wide x tall\"]",
+    ] {
+        let error = parse_error(&format!("{}\n{}", HEADER, event));
+        assert!(error.starts_with("invalid"), "{} gave {}", event, error);
+    }
+}
+
+#[test]
+fn recordings_round_trip() {
+    let mut backend = TestBackend::new(12, 2);
+    backend.push_event(Event::Key(Key::from_char('"').unwrap()));
+    backend.push_event(Event::Key(Key::new(InputKey::Enter)));
+    backend.push_event(Event::Resize(10, 2));
+    let mut cast = Vec::new();
+    {
+        let mut recorder = RecordingBackend::new(&mut backend, &mut cast).unwrap();
+        for _ in 0..3 {
+            recorder.poll_event(Duration::ZERO).unwrap();
+            recorder.set_sensitive(false);
+        }
+        recorder.draw([(0, 0, &Cell::new("\\"))].into_iter()).unwrap();
+        recorder.flush().unwrap();
+    }
+
+    let recording = Recording::parse(&String::from_utf8(cast).unwrap()).unwrap();
+    assert_eq!((recording.width, recording.height), (12, 2));
+    let events = recording.input_events().into_iter().map(|(_, e)| e).collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [Event::Key(Key::from_char('"').unwrap()), Event::Key(Key::new(InputKey::Enter)), Event::Resize(10, 2)]
+    );
+    assert!(matches!(&recording.events[3].1, CastEvent::Output(o) if o.contains('\\')));
+}
+
+struct Counter(u32);
+
+impl App for Counter {
+    fn display(&mut self, ui: &mut Ui) {
+        ui.text(format!("count {}", self.0).into());
+    }
+
+    fn update(&mut self, key: Option<Key>) -> bool {
+        if key.is_some() {
+            self.0 += 1;
+        }
+        true
+    }
+
+    fn get_fps(&self) -> u32 {
+        100
+    }
+}
+
+#[test]
+fn replay_stays_on_the_last_frame_until_the_recording_ends() {
+    let cast = format!("{}\n[0.0, \"i\", \"a\"]\n[0.3, \"o\", \"the end\"]\n", HEADER);
+    let recording = Recording::parse(&cast).unwrap();
+    let mut inner = TestBackend::new(40, 10);
+
+    let started = Instant::now();
+    AppRuntime::new().run_with(Counter(0), ReplayBackend::new(&recording, &mut inner)).unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(300));
+    assert_eq!(inner.buffer().to_plain_string().lines().next(), Some("count 1"));
+}
+
+#[test]
+fn replay_ends_when_the_inner_backend_quits() {
+    let cast = format!("{}\n[0.0, \"i\", \"a\"]\n[30.0, \"o\", \"the end\"]\n", HEADER);
+    let recording = Recording::parse(&cast).unwrap();
+    let mut inner = TestBackend::new(40, 10);
+    inner.push_event(Event::Quit);
+
+    let started = Instant::now();
+    AppRuntime::new().run_with(Counter(0), ReplayBackend::new(&recording, &mut inner)).unwrap();
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
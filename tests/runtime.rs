@@ -0,0 +1,75 @@
+use std::io;
+use std::time::Duration;
+use tuitui::prelude::*;
+
+struct Quits;
+
+impl App for Quits {
+    fn display(&mut self, ui: &mut Ui) {
+        ui.text("hi".into());
+    }
+
+    fn update(&mut self, key: Option<Key>) -> bool {
+        key.is_none()
+    }
+
+    fn get_fps(&self) -> u32 {
+        1000
+    }
+}
+
+/// Fails to report its size, which fails the first frame
+struct Broken(TestBackend);
+
+impl Backend for Broken {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.0.draw(content)
+    }
+
+    fn move_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.0.move_cursor(x, y)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.0.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.0.show_cursor()
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.0.clear()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Err(io::Error::other("no terminal"))
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        self.0.poll_event(timeout)
+    }
+}
+
+#[test]
+fn the_cursor_comes_back_after_a_clean_exit() {
+    let mut backend = TestBackend::new(10, 2);
+    backend.push_event(Event::Key(Key::new(InputKey::Esc)));
+    AppRuntime::new().with_hyperlinks(true).run_with(Quits, &mut backend).unwrap();
+    assert!(backend.is_cursor_visible());
+}
+
+#[test]
+fn the_cursor_comes_back_when_a_frame_fails() {
+    let mut backend = Broken(TestBackend::new(10, 2));
+    let error = AppRuntime::new().with_hyperlinks(true).run_with(Quits, &mut backend).unwrap_err();
+    assert_eq!(error.to_string(), "no terminal");
+    assert!(backend.0.is_cursor_visible());
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "server")]
+
+use std::io::Cursor;
+use tuitui::prelude::*;
+
+/// Shows the keys typed so far and the width it's drawn at, and quits on `q`
+#[derive(Default)]
+struct Echo {
+    typed: String,
+}
+
+impl App for Echo {
+    fn display(&mut self, ui: &mut Ui) {
+        ui.text(format!("[{}] {}", ui.width().unwrap_or(0), self.typed).into());
+    }
+
+    fn update(&mut self, key: Option<Key>) -> bool {
+        match key {
+            Some(key) if key.key == InputKey::Q => false,
+            Some(key) if key.key == InputKey::Enter => {
+                self.typed.push('⏎');
+                true
+            }
+            Some(key) => {
+                self.typed.extend(key.character);
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn get_fps(&self) -> u32 {
+        1000
+    }
+}
+
+fn session_bytes(server: &AppServer<fn() -> Echo>, input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    server.serve_session(Cursor::new(input.to_vec()), &mut output).unwrap();
+    output
+}
+
+fn session(server: &AppServer<fn() -> Echo>, input: &[u8]) -> String {
+    String::from_utf8_lossy(&session_bytes(server, input)).into_owned()
+}
+
+fn server() -> AppServer<fn() -> Echo> {
+    AppServer::new(Echo::default as fn() -> Echo).with_default_size(20, 3)
+}
+
+#[test]
+fn session_switches_screens_and_asks_for_the_size() {
+    let output = session(&server(), b"q");
+    assert!(output.starts_with("\x1b[?1049h\x1b[18t"));
+    assert!(output.ends_with("\x1b[0m\x1b[?1049l"));
+}
+
+#[test]
+fn typed_keys_reach_the_app() {
+    // The size report redraws the whole screen, so the line shows up in one piece
+    let output = session(&server(), b"hi\x1b[8;3;20tq");
+    assert!(output.contains("[20] hi"), "{:?}", output);
+}
+
+#[test]
+fn size_reports_resize_the_session() {
+    let output = session(&server(), b"\x1b[8;4;32tq");
+    assert!(output.contains("[32]"), "{:?}", output);
+}
+
+#[test]
+fn disconnecting_ends_the_session() {
+    // No `q`: the end of the input stream quits the app
+    let output = session(&server(), b"ab");
+    assert!(output.ends_with("\x1b[?1049l"));
+}
+
+#[test]
+fn telnet_negotiates_and_strips_commands() {
+    const IAC: u8 = 255;
+    const SB: u8 = 250;
+    const SE: u8 = 240;
+    const WILL: u8 = 251;
+    const NAWS: u8 = 31;
+
+    let server = server().with_telnet(true);
+    let mut input = vec![IAC, WILL, NAWS, IAC, SB, NAWS, 0, 40, 0, 6, IAC, SE];
+    input.extend_from_slice(b"ok\r\n");
+    input.extend_from_slice(&[IAC, SB, NAWS, 0, 40, 0, 6, IAC, SE]);
+    input.push(b'q');
+    let output = session_bytes(&server, &input);
+
+    // IAC WILL ECHO, IAC WILL SUPPRESS-GO-AHEAD, IAC DO NAWS
+    assert!(output.starts_with(&[IAC, WILL, 1, IAC, WILL, 3, IAC, 253, NAWS]));
+    let output = String::from_utf8_lossy(&output);
+    assert!(output.contains("[40] ok⏎"), "{:?}", output);
+}
+
+#[cfg(unix)]
+#[test]
+fn serves_over_a_unix_socket() {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::{Duration, Instant};
+
+    let path = std::env::temp_dir().join(format!("tuitui-test-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listening = path.clone();
+    std::thread::spawn(move || server().serve_unix(listening));
+
+    let started = Instant::now();
+    let mut stream = loop {
+        match UnixStream::connect(&path) {
+            Ok(stream) => break stream,
+            Err(_) if started.elapsed() < Duration::from_secs(5) => std::thread::sleep(Duration::from_millis(10)),
+            Err(e) => panic!("the server never started listening: {}", e),
+        }
+    };
+    stream.write_all(b"yo").unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    stream.write_all(b"\x1b[8;3;20tq").unwrap();
+
+    let mut output = String::new();
+    stream.read_to_string(&mut output).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(output.contains("[20] yo"), "{:?}", output);
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tuitui::prelude::*;
+
+/// The tests that look at `UPDATE_SNAPSHOTS_VAR` take turns, since the environment is shared by the whole process
+static ENV: Mutex<()> = Mutex::new(());
+
+fn widget_ui() -> Ui {
+    let mut ui = Ui::new();
+    ui.widget(|w| w.with_contents(markup!("[bold red]error[/]: 漢字 not found\nretry?")).with_height(4).build());
+    ui
+}
+
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tuitui-snapshots-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+
+fn frame(text: &str) -> Buffer {
+    let mut ui = Ui::new();
+    ui.text(text.into());
+    ui.to_frame(12, 2)
+}
+
+#[test]
+fn widget_fits_styled_and_wide_contents() {
+    // Measured in display columns, so neither the escape sequences nor the wide characters break the border
+    assert_frame_snapshot!(widget_ui(), 30, 5);
+}
+
+
+#[test]
+fn widget_at_a_fixed_width() {
+    let mut ui = Ui::new();
+    ui.widget(|w| w.with_contents("Hi".into()).with_width(8).with_height(3).with_alignment(Alignment::Center).build());
+    ui.widget(|w| w.with_contents("far too wide".into()).with_width(6).with_height(3).build());
+    assert_frame_snapshot!(name: "widget_fixed_width", ui, 14, 6);
+}
+
+
+#[test]
+fn mismatches_panic_with_a_diff() {
+    let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = temp_dir("mismatch");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("frame.snap"), Snapshot::from_buffer(&frame("before")).to_file_contents()).unwrap();
+
+    let result = panic::catch_unwind(|| assert_snapshot(&dir, "frame", &frame("after")));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("does not match"));
+    assert!(message.contains("- before\n+ after\n"));
+    assert!(message.contains(UPDATE_SNAPSHOTS_VAR));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+
+#[test]
+fn missing_snapshots_panic_with_the_frame() {
+    let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = temp_dir("missing");
+
+    let result = panic::catch_unwind(|| assert_snapshot(&dir, "frame", &frame("new")));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("does not exist"));
+    assert!(message.contains("--- text\nnew\n"));
+    assert!(!dir.exists());
+}
+
+
+#[test]
+fn update_variable_writes_the_goldens() {
+    let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = temp_dir("update");
+
+    std::env::set_var(UPDATE_SNAPSHOTS_VAR, "1");
+    assert_snapshot(&dir, "frame", &frame("first"));
+    assert_snapshot(&dir, "frame", &frame("second"));
+    std::env::remove_var(UPDATE_SNAPSHOTS_VAR);
+
+    let written = fs::read_to_string(dir.join("frame.snap")).unwrap();
+    assert_eq!(written, Snapshot::from_buffer(&frame("second")).to_file_contents());
+    // Without the variable the new golden is what frames are compared to
+    assert_snapshot(&dir, "frame", &frame("second"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,97 @@
+use tuitui::prelude::*;
+
+#[test]
+fn patch_takes_colors_from_the_top_style() {
+    let parent = Style::new().with_fg(Color::Blue).with_bg(Color::Black);
+    let child = Style::new().with_fg(Color::Red);
+    let patched = parent.patch(child);
+
+    assert_eq!(patched.fg, Some(Color::Red));
+    assert_eq!(patched.bg, Some(Color::Black));
+    assert_eq!(Style::new().patch(parent), parent);
+    assert_eq!(parent.patch(Style::new()), parent);
+}
+
+#[test]
+fn patch_adds_and_removes_modifiers() {
+    let parent = Style::from(Modifier::Bold).with_modifier(Modifier::Italic);
+    let child = Style::new().without_modifier(Modifier::Italic).with_modifier(Modifier::Underlined);
+    let patched = parent.patch(child);
+
+    assert!(patched.has(Modifier::Bold));
+    assert!(!patched.has(Modifier::Italic));
+    assert!(patched.has(Modifier::Underlined));
+
+    // A removal survives being patched with a style that doesn't mention the modifier
+    let removed = child.patch(Style::from(Modifier::Dim));
+    assert!(!Style::from(Modifier::Italic).patch(removed).has(Modifier::Italic));
+}
+
+#[test]
+fn with_and_without_modifier_cancel_each_other() {
+    let style = Style::new().with_modifier(Modifier::Bold).without_modifier(Modifier::Bold);
+    assert!(!style.has(Modifier::Bold));
+    assert!(style.is_plain());
+
+    let style = style.with_modifier(Modifier::Bold);
+    assert!(style.has(Modifier::Bold));
+    assert_eq!(style.sub_modifier, Modifiers::default());
+}
+
+#[test]
+fn inherit_keeps_span_styles_over_the_parent() {
+    let text = text!(span!("a"), span!("b", color Color::Red, bold));
+    let inherited = text.inherit(Style::new().with_fg(Color::Blue).with_modifier(Modifier::Italic));
+    let styles: Vec<_> = inherited.spans().iter().map(|s| s.get_style()).collect();
+
+    assert_eq!(styles[0].fg, Some(Color::Blue));
+    assert!(styles[0].has(Modifier::Italic));
+    assert_eq!(styles[1].fg, Some(Color::Red));
+    assert!(styles[1].has(Modifier::Bold) && styles[1].has(Modifier::Italic));
+}
+
+#[test]
+fn patch_style_lays_a_style_over_every_span() {
+    let mut text = text!(span!("a", color Color::Red), span!("b"));
+    text.patch_style(Style::new().with_fg(Color::Green));
+    assert!(text.spans().iter().all(|s| s.get_style().fg == Some(Color::Green)));
+}
+
+#[test]
+fn sgr_only_sends_what_changed() {
+    let plain = Style::new();
+    let bold = Style::from(Modifier::Bold);
+    let bold_red = bold.with_fg(Color::Red);
+
+    assert_eq!(plain.sgr(&plain), "");
+    assert_eq!(bold.sgr(&plain), "\x1b[1m");
+    assert_eq!(bold_red.sgr(&bold), "\x1b[31m");
+    assert_eq!(bold.sgr(&bold_red), "\x1b[39m");
+    assert_eq!(bold_red.sgr(&bold_red), "");
+    assert_eq!(plain.sgr(&bold_red), "\x1b[0m");
+}
+
+#[test]
+fn sgr_brings_back_bold_when_dim_is_turned_off() {
+    let both = Style::from(Modifier::Bold).with_modifier(Modifier::Dim);
+    let bold = Style::from(Modifier::Bold);
+    // Both are turned off by 22, so bold is sent again
+    assert_eq!(bold.sgr(&both), "\x1b[22;1m");
+}
+
+#[test]
+fn rendered_spans_switch_styles_minimally() {
+    let text = text!(span!("a", bold), span!("b", bold, color Color::Red), span!("c"));
+    assert_eq!(text.render(), "\x1b[1ma\x1b[31mb\x1b[0mc");
+}
+
+#[test]
+fn parsed_ansi_keeps_the_styles() {
+    let text = Text::from_ansi("\x1b[1;31mred\x1b[22mthin\x1b[0mplain");
+    let styles: Vec<_> = text.spans().iter().map(|s| (s.render_plain(), s.get_style())).collect();
+
+    assert_eq!(styles[0], ("red".to_string(), Style::from(Modifier::Bold).with_fg(Color::Red)));
+    assert_eq!(styles[1].1.fg, Some(Color::Red));
+    assert!(!styles[1].1.has(Modifier::Bold));
+    assert!(styles[2].1.is_plain());
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "code")]
+
+use tuitui::prelude::*;
+
+/// The colored tokens of `source`, each named after the role its style comes from. Plain text is left out
+fn tokens(source: &str, language: Language) -> Vec<(String, &'static str)> {
+    let theme = Theme::dark();
+    let roles = [
+        ("keyword", Style::new().with_fg(theme.primary).with_modifier(Modifier::Bold)),
+        ("key", Style::new().with_fg(theme.primary)),
+        ("string", Style::new().with_fg(theme.success)),
+        // Constants like `true` and shell flags share the color of numbers
+        ("number", Style::new().with_fg(theme.warning)),
+        ("comment", Style::new().with_fg(theme.muted).with_modifier(Modifier::Italic)),
+        ("attribute", Style::new().with_fg(theme.muted)),
+        ("type", Style::new().with_fg(theme.secondary)),
+        ("function", Style::new().with_fg(theme.accent)),
+        ("variable", Style::new().with_fg(theme.focus)),
+    ];
+
+    Code::new(source, language)
+        .highlighted(&theme)
+        .spans()
+        .iter()
+        .filter(|span| !span.get_style().is_plain())
+        .map(|span| {
+            let role = roles.iter().find(|(_, style)| *style == span.get_style()).map(|(role, _)| *role);
+            (span.render_plain(), role.unwrap_or_else(|| panic!("{:?} has no role", span.get_style())))
+        })
+        .collect()
+}
+
+
+fn owned(expected: &[(&str, &'static str)]) -> Vec<(String, &'static str)> {
+    expected.iter().map(|(token, role)| (token.to_string(), *role)).collect()
+}
+
+#[test]
+fn tokens_cover_the_whole_source() {
+    let source = "fn main() {\n    let x = r#\"a \"b\"\"#; // done\n}";
+    for language in [Language::Rust, Language::Toml, Language::Json, Language::Yaml, Language::Shell, Language::Plain] {
+        assert_eq!(Code::new(source, language).highlighted(&Theme::dark()).render_plain(), source);
+    }
+}
+
+
+#[test]
+fn strings_keep_their_escaped_quotes() {
+    assert_eq!(tokens(r#"let s = "a \"q\" b";"#, Language::Rust), owned(&[("let", "keyword"), (r#""a \"q\" b""#, "string")]));
+    assert_eq!(tokens(r#"name = "tui\"tui""#, Language::Toml), owned(&[("name", "key"), (r#""tui\"tui""#, "string")]));
+    assert_eq!(
+        tokens(r#"{"key": "va\"lue", "n": 1.5, "b": null}"#, Language::Json),
+        owned(&[
+            (r#""key""#, "key"),
+            (r#""va\"lue""#, "string"),
+            (r#""n""#, "key"),
+            ("1.5", "number"),
+            (r#""b""#, "key"),
+            ("null", "number"),
+        ])
+    );
+    assert_eq!(tokens("k: 'it''s'", Language::Yaml), owned(&[("k", "key"), ("'it''s'", "string")]));
+}
+
+
+#[test]
+fn line_and_block_comments() {
+    assert_eq!(
+        tokens("x; // note\n/* block\n still */ y", Language::Rust),
+        owned(&[("// note", "comment"), ("/* block\n still */", "comment")])
+    );
+    assert_eq!(
+        tokens("# top\nversion = 3 # trailing", Language::Toml),
+        owned(&[("# top", "comment"), ("version", "key"), ("3", "number"), ("# trailing", "comment")])
+    );
+    assert_eq!(tokens("# c\nport: 8080 # c2", Language::Yaml), owned(&[("# c", "comment"), ("port", "key"), ("8080", "number"), ("# c2", "comment")]));
+    // Only a `#` starting a word opens a comment
+    assert_eq!(tokens("echo a#b # c", Language::Shell), owned(&[("echo", "function"), ("# c", "comment")]));
+}
+
+
+#[test]
+fn rust_raw_strings() {
+    assert_eq!(
+        tokens(r###"let r = r#"raw "x""#; let b = br##"a"#b"##;"###, Language::Rust),
+        owned(&[
+            ("let", "keyword"),
+            (r##"r#"raw "x""#"##, "string"),
+            ("let", "keyword"),
+            (r###"br##"a"#b"##"###, "string"),
+        ])
+    );
+}
+
+
+#[test]
+fn rust_lifetimes_are_not_char_literals() {
+    assert_eq!(
+        tokens(r"fn f<'a>(x: &'a str) { let c = 'a'; let n = '\n'; }", Language::Rust),
+        owned(&[
+            ("fn", "keyword"),
+            ("'a", "variable"),
+            ("'a", "variable"),
+            ("let", "keyword"),
+            ("'a'", "string"),
+            ("let", "keyword"),
+            (r"'\n'", "string"),
+        ])
+    );
+}
+
+
+#[test]
+fn rust_macros_attributes_and_types() {
+    assert_eq!(
+        tokens("#[derive(Debug)]\nstruct S;\nprintln!(\"{}\", 42);", Language::Rust),
+        owned(&[
+            ("#[derive(Debug)]", "attribute"),
+            ("struct", "keyword"),
+            ("S", "type"),
+            ("println!", "function"),
+            ("\"{}\"", "string"),
+            ("42", "number"),
+        ])
+    );
+}
+
+
+#[test]
+fn toml_tables_are_types() {
+    assert_eq!(
+        tokens("[package]\nok = true", Language::Toml),
+        owned(&[("[package]", "type"), ("ok", "key"), ("true", "number")])
+    );
+}
+
+
+#[test]
+fn yaml_keys_and_values() {
+    assert_eq!(
+        tokens("name: value\nlist:\n  - \"quoted: x\"\n  - true\nanchor: &base", Language::Yaml),
+        owned(&[
+            ("name", "key"),
+            ("value", "string"),
+            ("list", "key"),
+            ("\"quoted: x\"", "string"),
+            ("true", "number"),
+            ("anchor", "key"),
+            ("&base", "variable"),
+        ])
+    );
+}
+
+
+#[test]
+fn shell_variables() {
+    assert_eq!(
+        tokens("echo $HOME ${USER} $1 '$NOT'", Language::Shell),
+        owned(&[("echo", "function"), ("$HOME", "variable"), ("${USER}", "variable"), ("$1", "variable"), ("'$NOT'", "string")])
+    );
+}
+
+
+#[test]
+fn shell_assignments() {
+    assert_eq!(tokens("COUNT=3", Language::Shell), owned(&[("COUNT", "variable"), ("3", "string")]));
+    // An assignment in front of a command leaves the command a command, quoted or not
+    assert_eq!(
+        tokens("NAME=\"a \\\"b\\\"\" make build", Language::Shell),
+        owned(&[("NAME", "variable"), ("\"a \\\"b\\\"\"", "string"), ("make", "function")])
+    );
+    assert_eq!(tokens("CC=gcc make", Language::Shell), owned(&[("CC", "variable"), ("gcc", "string"), ("make", "function")]));
+}
+
+
+#[test]
+fn shell_keywords_start_commands() {
+    assert_eq!(
+        tokens("if [ -n \"$X\" ]; then exit 0; fi", Language::Shell),
+        owned(&[
+            ("if", "keyword"),
+            ("[", "function"),
+            ("-n", "number"),
+            ("\"$X\"", "string"),
+            ("then", "keyword"),
+            ("exit", "function"),
+            ("0", "number"),
+            ("fi", "keyword"),
+        ])
+    );
+}
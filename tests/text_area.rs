@@ -0,0 +1,190 @@
+use tuitui::prelude::*;
+
+fn screen(area: &TextArea) -> String {
+    area.to_text(&Theme::default()).render_plain()
+}
+
+fn press(area: &mut TextArea, key: InputKey) {
+    area.handle_key(Key::new(key));
+}
+
+fn ctrl(key: InputKey) -> Key {
+    Key::new(key).with_modifiers(KeyModifiers::CONTROL)
+}
+
+fn shift(key: InputKey) -> Key {
+    Key::new(key).with_modifiers(KeyModifiers::SHIFT)
+}
+
+fn type_str(area: &mut TextArea, text: &str) {
+    for c in text.chars() {
+        area.handle_key(Key::from_char(c).unwrap());
+    }
+}
+
+#[test]
+fn soft_wrap_breaks_lines_between_words() {
+    let area = TextArea::new().with_contents("hello world again\nend").with_size(10, 4).with_soft_wrap(true).with_focus(false);
+    assert_eq!(screen(&area), "hello \nworld \nagain\nend");
+
+    // Without soft wrap, lines run past the edge and are cut
+    let area = area.with_soft_wrap(false);
+    assert_eq!(screen(&area), "hello worl\nend\n\n");
+}
+
+#[test]
+fn up_and_down_move_through_wrapped_rows() {
+    let mut area = TextArea::new().with_contents("hello world again").with_size(10, 3).with_soft_wrap(true);
+    area.set_cursor(0, 2);
+    press(&mut area, InputKey::DownArrow);
+    assert_eq!(area.cursor(), (0, 8));
+    press(&mut area, InputKey::DownArrow);
+    assert_eq!(area.cursor(), (0, 14));
+    press(&mut area, InputKey::UpArrow);
+    assert_eq!(area.cursor(), (0, 8));
+}
+
+#[test]
+fn scrolls_vertically_to_follow_the_cursor() {
+    // Contents put the cursor at the end
+    let mut area = TextArea::new().with_contents("1\n2\n3\n4\n5").with_size(10, 2).with_focus(false);
+    assert_eq!(screen(&area), "4\n5");
+
+    press(&mut area, InputKey::PageUp);
+    assert_eq!(area.cursor().0, 2);
+    assert_eq!(screen(&area), "3\n4");
+
+    area.handle_key(ctrl(InputKey::Home));
+    assert_eq!(screen(&area), "1\n2");
+}
+
+#[test]
+fn scrolls_horizontally_to_follow_the_cursor() {
+    let mut area = TextArea::new().with_contents("0123456789abcdef").with_size(6, 1).with_focus(false);
+    press(&mut area, InputKey::End);
+    assert_eq!(screen(&area), "bcdef");
+
+    press(&mut area, InputKey::Home);
+    assert_eq!(screen(&area), "012345");
+}
+
+#[test]
+fn ctrl_arrows_jump_over_words() {
+    let mut area = TextArea::new().with_contents("one  two three\nnext");
+    area.set_cursor(0, 0);
+    area.handle_key(ctrl(InputKey::RightArrow));
+    assert_eq!(area.cursor(), (0, 3));
+    area.handle_key(ctrl(InputKey::RightArrow));
+    assert_eq!(area.cursor(), (0, 8));
+
+    press(&mut area, InputKey::End);
+    area.handle_key(ctrl(InputKey::RightArrow));
+    assert_eq!(area.cursor(), (1, 0));
+
+    area.handle_key(ctrl(InputKey::LeftArrow));
+    assert_eq!(area.cursor(), (0, 14));
+    area.handle_key(ctrl(InputKey::LeftArrow));
+    assert_eq!(area.cursor(), (0, 9));
+    area.handle_key(ctrl(InputKey::LeftArrow));
+    assert_eq!(area.cursor(), (0, 5));
+}
+
+#[test]
+fn selections_run_across_lines() {
+    let mut area = TextArea::new().with_contents("first\nsecond\nthird");
+    area.set_cursor(0, 2);
+    area.handle_key(shift(InputKey::DownArrow));
+    area.handle_key(shift(InputKey::DownArrow));
+    assert_eq!(area.selection().as_deref(), Some("rst\nsecond\nth"));
+
+    // Without shift the cursor leaves the selection at its start
+    press(&mut area, InputKey::LeftArrow);
+    assert_eq!(area.selection(), None);
+    assert_eq!(area.cursor(), (0, 2));
+}
+
+#[test]
+fn cut_and_paste_move_the_selection() {
+    let mut area = TextArea::new().with_contents("one\ntwo");
+    area.set_cursor(0, 1);
+    area.handle_key(shift(InputKey::DownArrow));
+    area.handle_key(ctrl(InputKey::X));
+    assert_eq!(area.contents(), "owo");
+    assert_eq!(area.clipboard(), "ne\nt");
+    assert_eq!(area.take_copied().as_deref(), Some("ne\nt"));
+
+    area.handle_key(ctrl(InputKey::End));
+    area.handle_key(ctrl(InputKey::V));
+    assert_eq!(area.contents(), "owone\nt");
+    assert_eq!(area.cursor(), (1, 1));
+
+    // Pasting over a selection replaces it
+    area.handle_key(ctrl(InputKey::A));
+    area.handle_key(ctrl(InputKey::V));
+    assert_eq!(area.contents(), "ne\nt");
+}
+
+#[test]
+fn typing_is_undone_a_word_at_a_time() {
+    let mut area = TextArea::new();
+    type_str(&mut area, "one two");
+    press(&mut area, InputKey::Backspace);
+    press(&mut area, InputKey::Backspace);
+    assert_eq!(area.contents(), "one t");
+
+    assert!(area.undo());
+    assert_eq!(area.contents(), "one two");
+    assert!(area.undo());
+    assert_eq!(area.contents(), "one ");
+    assert!(area.undo());
+    assert_eq!(area.contents(), "one");
+    assert!(area.undo());
+    assert_eq!(area.contents(), "");
+    assert!(!area.undo());
+
+    assert!(area.redo());
+    assert_eq!(area.contents(), "one");
+}
+
+#[test]
+fn moving_the_cursor_ends_an_undo_group() {
+    let mut area = TextArea::new();
+    type_str(&mut area, "ab");
+    press(&mut area, InputKey::LeftArrow);
+    type_str(&mut area, "cd");
+    assert_eq!(area.contents(), "acdb");
+
+    area.undo();
+    assert_eq!(area.contents(), "ab");
+}
+
+#[test]
+fn deleting_nothing_leaves_nothing_to_undo() {
+    let mut area = TextArea::new().with_contents("ab");
+    area.set_cursor(0, 0);
+    assert!(!area.handle_key(Key::new(InputKey::Backspace)));
+    area.handle_key(ctrl(InputKey::End));
+    assert!(!area.handle_key(Key::new(InputKey::Delete)));
+    assert!(!area.undo());
+
+    type_str(&mut area, "c");
+    press(&mut area, InputKey::Home);
+    press(&mut area, InputKey::Backspace);
+    assert!(area.undo());
+    assert_eq!(area.contents(), "ab");
+    assert!(!area.undo());
+}
+
+#[test]
+fn history_keeps_the_last_hundred_edits() {
+    let mut area = TextArea::new();
+    for i in 0..150 {
+        area.insert_str(&format!("{} ", i));
+    }
+    let mut undone = 0;
+    while area.undo() {
+        undone += 1;
+    }
+    assert_eq!(undone, 100);
+    assert_eq!(area.contents(), (0..50).map(|i| format!("{} ", i)).collect::<String>());
+}
@@ -0,0 +1,108 @@
+use tuitui::prelude::*;
+
+fn sample(ui: &mut Ui) -> String {
+    ui.heading("Title".into())
+        .separator("-".into(), 5)
+        .widget(|w| w.with_contents("Hi".into()).with_height(3).build());
+    ui.render()
+}
+
+#[test]
+fn default_theme_draws_without_colors() {
+    assert_eq!(Theme::default(), Theme::classic());
+    assert_eq!(sample(&mut Ui::new()), "\x1b[1mTitle\x1b[0m\n-----\n┌──┐\n│Hi│\n└──┘\n");
+}
+
+#[test]
+fn default_theme_leaves_the_screen_unfilled() {
+    let mut ui = Ui::new();
+    ui.text("Hi".into());
+    let snapshot = Snapshot::from_buffer(&ui.to_frame(4, 2));
+    assert!(snapshot.legend.is_empty());
+}
+
+#[test]
+fn dark_theme_is_opt_in() {
+    let output = sample(&mut Ui::new().with_theme(Theme::dark()));
+    assert!(output.starts_with("\x1b[1;38;2;164;143;214mTitle"));
+    assert!(output.contains('╭'));
+}
+
+#[cfg(feature = "theme-files")]
+#[test]
+fn theme_file_headings_follow_primary() {
+    let theme = Theme::from_toml(r##"
+        base = "dark"
+        primary = "#ff7e5f"
+    "##).unwrap();
+    assert_eq!(theme.heading_style.fg, Some(Color::RGB(255, 126, 95)));
+
+    let theme = Theme::from_toml(r#"border = "none""#).unwrap();
+    assert_eq!(theme.border, None);
+}
+
+#[cfg(feature = "theme-files")]
+#[test]
+fn theme_file_rejects_unknown_names() {
+    let error = Theme::from_toml(r#"base = "sepia""#).unwrap_err();
+    assert_eq!(error.details, "`base`: `sepia` isn't a built-in theme");
+
+    let error = Theme::from_json(r#"{ "border_style": "wavy" }"#).unwrap_err();
+    assert_eq!(error.details, "`border_style`: `wavy` isn't a border style");
+
+    assert!(Theme::from_toml(r#"base = "high-contrast""#).is_ok());
+    assert_eq!(Theme::try_from_name("sepia"), None);
+    assert_eq!(Theme::from_name("sepia"), Theme::default());
+    assert_eq!(WidgetStyle::from_name("wavy"), WidgetStyle::tuitui_classic());
+}
+
+#[cfg(feature = "theme-files")]
+#[test]
+fn theme_file_falls_back_to_the_default_theme() {
+    assert_eq!(Theme::from_toml(r#"name = "mine""#).unwrap().border_style, Theme::default().border_style);
+    assert_eq!(Theme::from_toml("").unwrap(), Theme::default());
+}
+
+/// Breaks its theme file on the first frame, then waits to see the reload fail
+#[cfg(feature = "theme-files")]
+struct BreaksTheme {
+    path: std::path::PathBuf,
+    frame: u32,
+    seen: std::rc::Rc<std::cell::RefCell<Option<(String, String)>>>,
+}
+
+#[cfg(feature = "theme-files")]
+impl App for BreaksTheme {
+    fn display(&mut self, ui: &mut Ui) {
+        if let Some(error) = ui.theme_error() {
+            *self.seen.borrow_mut() = Some((ui.theme().name.clone(), error.details.clone()));
+        }
+    }
+
+    fn update(&mut self, _key: Option<Key>) -> bool {
+        self.frame += 1;
+        if self.frame == 2 {
+            std::fs::write(&self.path, "base = \"sepia\"").unwrap();
+        }
+        self.seen.borrow().is_none() && self.frame < 2000
+    }
+
+    fn get_fps(&self) -> u32 {
+        1000
+    }
+}
+
+#[cfg(feature = "theme-files")]
+#[test]
+fn a_theme_file_that_stops_loading_keeps_the_last_theme() {
+    let path = std::env::temp_dir().join(format!("tuitui-theme-{}.toml", std::process::id()));
+    std::fs::write(&path, "base = \"light\"").unwrap();
+
+    let seen = std::rc::Rc::default();
+    let app = BreaksTheme { path: path.clone(), frame: 0, seen: std::rc::Rc::clone(&seen) };
+    AppRuntime::new().with_theme_file(&path).run_with(app, TestBackend::new(10, 2)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let expected = ("light".to_string(), "`base`: `sepia` isn't a built-in theme".to_string());
+    assert_eq!(*seen.borrow(), Some(expected));
+}
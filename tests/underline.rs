@@ -0,0 +1,111 @@
+use tuitui::prelude::*;
+
+#[test]
+fn underline_shapes_have_their_own_sgr() {
+    let plain = Style::new();
+    let shapes = [
+        (UnderlineStyle::Single, "\x1b[4m"),
+        (UnderlineStyle::Double, "\x1b[4:2m"),
+        (UnderlineStyle::Curly, "\x1b[4:3m"),
+        (UnderlineStyle::Dotted, "\x1b[4:4m"),
+        (UnderlineStyle::Dashed, "\x1b[4:5m"),
+    ];
+    for (shape, sgr) in shapes {
+        assert_eq!(Style::new().with_underline(shape).sgr(&plain), sgr);
+    }
+}
+
+#[test]
+fn changing_the_shape_sends_only_the_new_one() {
+    let single = Style::from(Modifier::Underlined);
+    let curly = Style::new().with_underline(UnderlineStyle::Curly);
+
+    assert_eq!(single.underline(), Some(UnderlineStyle::Single));
+    assert_eq!(curly.sgr(&single), "\x1b[4:3m");
+    assert_eq!(single.sgr(&curly), "\x1b[4m");
+    assert_eq!(Style::from(Modifier::Bold).sgr(&curly.with_modifier(Modifier::Bold)), "\x1b[24m");
+}
+
+#[test]
+fn the_shape_needs_the_underline_modifier() {
+    let style = Style::new().with_underline(UnderlineStyle::Double).without_modifier(Modifier::Underlined);
+    assert_eq!(style.underline(), None);
+    assert!(style.is_plain());
+}
+
+#[test]
+fn underline_colors() {
+    let plain = Style::new();
+    let underlined = Style::from(Modifier::Underlined);
+
+    assert_eq!(underlined.with_underline_color(Color::RGB(255, 0, 0)).sgr(&plain), "\x1b[4;58;2;255;0;0m");
+    assert_eq!(underlined.with_underline_color(Color::Indexed(196)).sgr(&plain), "\x1b[4;58;5;196m");
+    assert_eq!(underlined.with_underline_color(Color::Red).sgr(&plain), "\x1b[4;58;5;1m");
+    assert_eq!(underlined.sgr(&underlined.with_underline_color(Color::Red)), "\x1b[59m");
+}
+
+#[test]
+fn span_underlines() {
+    let text = text!(span!("typo", underline UnderlineStyle::Curly, underline_color Color::Red));
+    let style = text.spans()[0].get_style();
+    assert_eq!(style.underline(), Some(UnderlineStyle::Curly));
+    assert_eq!(style.underline_color, Some(Color::Red));
+}
+
+#[test]
+fn parsed_underlines() {
+    let cases = [
+        ("\x1b[4mx", Some(UnderlineStyle::Single)),
+        ("\x1b[4:2mx", Some(UnderlineStyle::Double)),
+        ("\x1b[21mx", Some(UnderlineStyle::Double)),
+        ("\x1b[4:3mx", Some(UnderlineStyle::Curly)),
+        ("\x1b[4:4mx", Some(UnderlineStyle::Dotted)),
+        ("\x1b[4:5mx", Some(UnderlineStyle::Dashed)),
+        ("\x1b[4:3m\x1b[4:0mx", None),
+        ("\x1b[4:3m\x1b[24mx", None),
+    ];
+    for (input, expected) in cases {
+        let text = Text::from_ansi(input);
+        assert_eq!(text.spans()[0].get_style().underline(), expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn parsed_underline_colors() {
+    let cases = [
+        ("\x1b[4;58;2;1;2;3mx", Some(Color::RGB(1, 2, 3))),
+        ("\x1b[4;58:2::1:2:3mx", Some(Color::RGB(1, 2, 3))),
+        ("\x1b[4;58;5;196mx", Some(Color::Indexed(196))),
+        ("\x1b[4;58;5;196m\x1b[59mx", None),
+    ];
+    for (input, expected) in cases {
+        let text = Text::from_ansi(input);
+        assert_eq!(text.spans()[0].get_style().underline_color, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn cells_keep_the_underline() {
+    let text = text!(span!("ab", underline UnderlineStyle::Dotted, underline_color Color::Blue));
+    let buffer = Buffer::from_ansi(&text.render(), 4, 1);
+    let cell = buffer.get(1, 0).unwrap();
+    assert_eq!(cell.underline_style, Some(UnderlineStyle::Dotted));
+    assert_eq!(cell.underline_color, Some(Color::Blue));
+    assert_eq!(buffer.get(2, 0).unwrap().underline_style, None);
+}
+
+#[cfg(feature = "ansi")]
+#[test]
+fn shapes_and_colors_need_a_capable_terminal() {
+    let cell = Cell::styled("x", Style::new().with_underline(UnderlineStyle::Curly).with_underline_color(Color::RGB(255, 0, 0)));
+    let draw = |depth: ColorDepth| {
+        let mut backend = AnsiBackend::new(Vec::new(), 1, 1);
+        backend.set_color_depth(depth);
+        backend.draw([(0, 0, &cell)].into_iter()).unwrap();
+        String::from_utf8(backend.writer().clone()).unwrap()
+    };
+
+    assert!(draw(ColorDepth::TrueColor).contains("\x1b[4:3;58;2;255;0;0m"));
+    assert!(draw(ColorDepth::Ansi256).contains("\x1b[4;58;5;196m"));
+    assert!(draw(ColorDepth::Ansi16).contains("\x1b[4mx"));
+}
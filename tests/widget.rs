@@ -0,0 +1,11 @@
+use tuitui::prelude::*;
+
+#[test]
+fn widgets_smaller_than_their_borders_still_render() {
+    let mut widget = Widget::new().with_contents("Hi".into()).build();
+    for (width, height) in [(0, 0), (1, 1), (2, 2)] {
+        widget.width = width;
+        widget.height = height;
+        assert_eq!(widget.render(), "┌┐\n└┘");
+    }
+}